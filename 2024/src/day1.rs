@@ -1,19 +1,18 @@
+use aoc_core::{
+    combinators::lines,
+    parse::{parse_puzzle, PuzzleParseError},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use nom::{
-    character::complete::{multispace1, newline, u32},
-    multi::separated_list0,
+    character::complete::{space1, u32},
     sequence::separated_pair,
 };
 use std::collections::BTreeMap;
 
 #[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> (Vec<u32>, Vec<u32>) {
-    separated_list0(
-        newline::<&str, nom::error::Error<&str>>,
-        separated_pair(u32, multispace1, u32),
-    )(input)
-    .map(|(_, result)| result.iter().cloned().collect())
-    .unwrap()
+pub fn input_generator(input: &str) -> Result<(Vec<u32>, Vec<u32>), PuzzleParseError> {
+    parse_puzzle(1, input, lines(separated_pair(u32, space1, u32)))
+        .map(|result| result.iter().cloned().collect())
 }
 
 #[aoc(day1, part1)]
@@ -45,3 +44,21 @@ pub fn solve_part2(input: &(Vec<u32>, Vec<u32>)) -> u32 {
         .map(|x| x * occurances.get(x).unwrap_or(&0))
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day1() {
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+        let input = input_generator(input).unwrap();
+        assert_eq!(solve_part1(&input), 11);
+        assert_eq!(solve_part2(&input), 31);
+    }
+}
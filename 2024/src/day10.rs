@@ -1,49 +1,32 @@
+use aoc_core::{dir::Dir4, grid::Grid, parse::PuzzleParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use glam::IVec2;
 use itertools::Itertools;
-use nom::{
-    character::complete::{line_ending, satisfy},
-    multi::many1,
-    sequence::terminated,
-    IResult, Parser,
-};
-use vecgrid::Vecgrid;
-
-fn end_of_line(input: &str) -> IResult<&str, &str> {
-    if input.is_empty() {
-        Ok((input, input))
-    } else {
-        line_ending(input)
-    }
-}
 
 type Trail = Vec<IVec2>;
 
 #[derive(Debug)]
-struct Map {
-    grid: Vecgrid<u8>,
+pub struct Map {
+    grid: Grid<u8>,
 }
 
 impl Map {
     fn get_point(&self, position: IVec2) -> Option<u8> {
-        self.grid
-            .get(position.y as usize, position.x as usize)
-            .copied()
+        self.grid.get(position).copied()
     }
 
-    fn trails_from(&self, position: IVec2, previous: &Trail) -> Vec<Trail> {
+    fn trails_from(&self, position: IVec2, previous: &[IVec2]) -> Vec<Trail> {
         match self.get_point(position) {
-            None => vec![previous.clone()],
+            None => vec![previous.to_vec()],
             Some(current) => {
-                let mut previous = previous.clone();
+                let mut previous = previous.to_vec();
                 previous.push(position);
 
                 if current == 9 {
-                    return vec![previous];
+                    vec![previous]
                 } else {
-                    [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
-                        .into_iter()
-                        .filter(|direction| {
+                    Dir4::iter()
+                        .filter(|&direction| {
                             self.get_point(position + direction)
                                 .is_some_and(|point| point == current + 1)
                         })
@@ -61,12 +44,10 @@ impl Map {
 
     fn trails(&self) -> Vec<Trail> {
         self.grid
-            .enumerate_column_major()
+            .iter()
             .filter_map(|(position, point)| {
                 if *point == 0 {
-                    Some(
-                        self.trails_from(IVec2::new(position.1 as i32, position.0 as i32), &vec![]),
-                    )
+                    Some(self.trails_from(position, &[]))
                 } else {
                     None
                 }
@@ -77,17 +58,10 @@ impl Map {
 }
 
 #[aoc_generator(day10)]
-pub fn input_generator(input: &str) -> Map {
-    let rows = many1(terminated(
-        many1(satisfy(|c| c.is_digit(10)).map(|c| c.to_digit(10).unwrap() as u8)),
-        end_of_line,
-    ))(input)
-    .unwrap()
-    .1;
-
-    Map {
-        grid: Vecgrid::from_rows(rows).expect("invalid input"),
-    }
+pub fn input_generator(input: &str) -> Result<Map, PuzzleParseError> {
+    Ok(Map {
+        grid: Grid::parse_with(10, input, |c| c.to_digit(10).map(|d| d as u8))?,
+    })
 }
 
 #[aoc(day10, part1)]
@@ -111,6 +85,31 @@ pub fn solve_part2(map: &Map) -> usize {
         .sum()
 }
 
+/// Random maps for stress testing.
+pub mod generate {
+    use aoc_core::rng::Rng;
+
+    /// A `size` by `size` map of gentle slopes, where each height is within one of the height
+    /// above it, so that there are plenty of trails.
+    pub fn input(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let mut above: Vec<i64> = (0..size).map(|_| rng.range(0..10)).collect();
+        let mut rows = Vec::with_capacity(size);
+        for _ in 0..size {
+            for height in &mut above {
+                *height = (*height + rng.range(-1..2)).clamp(0, 9);
+            }
+            rows.push(
+                above
+                    .iter()
+                    .map(|height| height.to_string())
+                    .collect::<String>(),
+            );
+        }
+        rows.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,7 +125,7 @@ mod tests {
 01329801
 10456732";
 
-        let map = input_generator(&input);
+        let map = input_generator(input).unwrap();
 
         assert_eq!(solve_part1(&map), 36);
     }
@@ -142,8 +141,16 @@ mod tests {
 01329801
 10456732";
 
-        let map = input_generator(&input);
+        let map = input_generator(input).unwrap();
 
         assert_eq!(solve_part2(&map), 81);
     }
+
+    #[test]
+    fn test_generated_input() {
+        for seed in 0..5 {
+            let map = input_generator(&generate::input(30, seed)).unwrap();
+            assert!(solve_part1(&map) <= solve_part2(&map));
+        }
+    }
 }
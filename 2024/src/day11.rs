@@ -1,13 +1,12 @@
 use std::collections::BTreeMap;
 
+use aoc_core::{
+    combinators::space_separated,
+    parse::{end_of_line, parse_puzzle, PuzzleParseError},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
-use nom::{
-    character::complete::{line_ending, space1, u64},
-    multi::separated_list1,
-    sequence::terminated,
-    IResult,
-};
+use nom::{character::complete::u64, sequence::terminated};
 
 #[derive(Clone)]
 pub struct Stones {
@@ -15,13 +14,11 @@ pub struct Stones {
 }
 
 impl Stones {
-    fn from_str(input: &str) -> Self {
-        let stones = terminated(separated_list1(space1, u64), end_of_line)(input)
-            .unwrap()
-            .1;
-        Self {
+    fn from_str(input: &str) -> Result<Self, PuzzleParseError> {
+        let stones = parse_puzzle(11, input, terminated(space_separated(u64), end_of_line))?;
+        Ok(Self {
             stones: stones.into_iter().counts().into_iter().collect(),
-        }
+        })
     }
 }
 
@@ -59,16 +56,8 @@ impl Iterator for Stones {
     }
 }
 
-fn end_of_line(input: &str) -> IResult<&str, &str> {
-    if input.is_empty() {
-        Ok((input, input))
-    } else {
-        line_ending(input)
-    }
-}
-
 #[aoc_generator(day11)]
-pub fn input_generator(input: &str) -> Stones {
+pub fn input_generator(input: &str) -> Result<Stones, PuzzleParseError> {
     Stones::from_str(input)
 }
 
@@ -91,7 +80,7 @@ mod test {
     #[test]
     fn test_stones() {
         let input = "125 17";
-        let mut stones = Stones::from_str(input);
+        let mut stones = Stones::from_str(input).unwrap();
         assert_eq!(stones.next(), Some(2));
         assert_eq!(stones.next(), Some(3));
         assert_eq!(stones.next(), Some(4));
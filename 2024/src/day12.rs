@@ -1,34 +1,20 @@
 use std::collections::{HashSet, VecDeque};
 
+use aoc_core::{dir::Dir4, grid::Grid, parse::PuzzleParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use glam::IVec2;
 use itertools::Itertools;
-use nom::{
-    character::complete::{line_ending, satisfy},
-    multi::many1,
-    sequence::terminated,
-    IResult,
-};
-use vecgrid::Vecgrid;
-
-fn end_of_line(input: &str) -> IResult<&str, &str> {
-    if input.is_empty() {
-        Ok((input, input))
-    } else {
-        line_ending(input)
-    }
-}
 
 #[derive(Clone, Debug)]
 pub struct Map {
-    grid: Vecgrid<char>,
+    grid: Grid<char>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Plot {
     position: IVec2,
     plant: char,
-    edges: Vec<IVec2>,
+    edges: Vec<Dir4>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -46,8 +32,8 @@ impl Region {
         self.plots.iter().map(|plot| plot.edges.len()).sum()
     }
 
-    fn edges_at(&self, position: IVec2, side: IVec2) -> Vec<Vec<i32>> {
-        let column = side.x != 0;
+    fn edges_at(&self, position: IVec2, side: Dir4) -> Vec<Vec<i32>> {
+        let column = side.is_horizontal();
         let mut result = Vec::new();
         let mut current_edge = Vec::new();
 
@@ -59,7 +45,7 @@ impl Region {
                     && p.edges.contains(&side)
             })
             .map(|p| if column { p.position.y } else { p.position.x })
-            .sorted_by(|a, b| a.cmp(&b))
+            .sorted()
         {
             if current_edge.is_empty() || p == current_edge.last().unwrap() + 1 {
                 current_edge.push(p);
@@ -76,7 +62,7 @@ impl Region {
         result
     }
 
-    fn num_edges_at(&self, position: IVec2, side: IVec2) -> usize {
+    fn num_edges_at(&self, position: IVec2, side: Dir4) -> usize {
         self.edges_at(position, side).len()
     }
 
@@ -89,15 +75,15 @@ impl Region {
             if !visited_columns.contains(&plot.position.x) {
                 visited_columns.insert(plot.position.x);
 
-                result += self.num_edges_at(plot.position, IVec2::NEG_X)
-                    + self.num_edges_at(plot.position, IVec2::X);
+                result += self.num_edges_at(plot.position, Dir4::W)
+                    + self.num_edges_at(plot.position, Dir4::E);
             }
 
             if !visited_rows.contains(&plot.position.y) {
                 visited_rows.insert(plot.position.y);
 
-                result += self.num_edges_at(plot.position, IVec2::NEG_Y)
-                    + self.num_edges_at(plot.position, IVec2::Y);
+                result += self.num_edges_at(plot.position, Dir4::N)
+                    + self.num_edges_at(plot.position, Dir4::S);
             }
         }
 
@@ -117,26 +103,15 @@ impl Region {
     }
 }
 
-const ALL_DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
-
 impl Map {
-    fn from_str(input: &str) -> Self {
-        let rows = many1(terminated(
-            many1(satisfy(|c| c.is_alphanumeric())),
-            end_of_line,
-        ))(input)
-        .unwrap()
-        .1;
-
-        Self {
-            grid: Vecgrid::from_rows(rows).expect("invalid input"),
-        }
+    fn from_str(input: &str) -> Result<Self, PuzzleParseError> {
+        Ok(Self {
+            grid: Grid::parse_with(12, input, |c| c.is_alphanumeric().then_some(c))?,
+        })
     }
 
     fn get_point(&self, position: IVec2) -> Option<char> {
-        self.grid
-            .get(position.y as usize, position.x as usize)
-            .copied()
+        self.grid.get(position).copied()
     }
 
     fn march_region(&self, start: IVec2) -> Option<Region> {
@@ -152,13 +127,12 @@ impl Map {
                 continue;
             };
 
-            if c != initial_c || plots.iter().find(|plot| plot.position == p).is_some() {
+            if c != initial_c || plots.iter().any(|plot| plot.position == p) {
                 continue;
             }
 
-            let edges = ALL_DIRECTIONS
-                .into_iter()
-                .filter(|d| !self.get_point(p + d).is_some_and(|c| c == initial_c))
+            let edges = Dir4::iter()
+                .filter(|&d| self.get_point(p + d).is_none_or(|c| c != initial_c))
                 .collect();
 
             plots.insert(Plot {
@@ -167,7 +141,7 @@ impl Map {
                 edges,
             });
 
-            for d in ALL_DIRECTIONS {
+            for d in Dir4::iter() {
                 queue.push_back(p + d);
             }
         }
@@ -181,8 +155,7 @@ impl Map {
     fn regions(&self) -> Vec<Region> {
         let mut regions: Vec<Region> = Vec::new();
 
-        for ((y, x), _) in self.grid.enumerate_column_major() {
-            let p = IVec2::new(x as i32, y as i32);
+        for p in self.grid.positions() {
             if regions.iter().any(|r| r.has_plot(p)) {
                 continue;
             }
@@ -209,7 +182,7 @@ impl Map {
 }
 
 #[aoc_generator(day12)]
-pub fn input_generator(input: &str) -> Map {
+pub fn input_generator(input: &str) -> Result<Map, PuzzleParseError> {
     Map::from_str(input)
 }
 
@@ -223,6 +196,33 @@ pub fn solve_part2(map: &Map) -> usize {
     map.fence_cost_edges()
 }
 
+/// Random maps for stress testing.
+pub mod generate {
+    use aoc_core::rng::Rng;
+
+    /// A `size` by `size` garden where most plots copy the plant to their left or above, so
+    /// regions grow into irregular blobs.
+    pub fn input(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let mut plants: Vec<char> = Vec::with_capacity(size * size);
+        for i in 0..size * size {
+            let plant = if i % size > 0 && rng.chance(0.45) {
+                plants[i - 1]
+            } else if i >= size && rng.chance(0.7) {
+                plants[i - size]
+            } else {
+                (b'A' + rng.below(26) as u8) as char
+            };
+            plants.push(plant);
+        }
+        plants
+            .chunks(size)
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -239,7 +239,7 @@ VVIIICJJEE
 MIIIIIJJEE
 MIIISIJEEE
 MMMISSJEEE";
-        let map = Map::from_str(input);
+        let map = Map::from_str(input).unwrap();
         assert_eq!(map.regions().len(), 11);
         assert_eq!(map.fence_cost_perimeter(), 1930);
     }
@@ -256,9 +256,17 @@ VVIIICJJEE
 MIIIIIJJEE
 MIIISIJEEE
 MMMISSJEEE";
-        let map = Map::from_str(input);
+        let map = Map::from_str(input).unwrap();
         let regions = map.regions();
         assert_eq!(regions.len(), 11);
         assert_eq!(map.fence_cost_edges(), 1206);
     }
+
+    #[test]
+    fn test_generated_input() {
+        for seed in 0..5 {
+            let map = input_generator(&generate::input(30, seed)).unwrap();
+            assert!(solve_part2(&map) <= solve_part1(&map));
+        }
+    }
 }
@@ -1,49 +1,38 @@
+use aoc_core::{
+    combinators::lines,
+    math::solve,
+    parse::{end_of_line, parse_puzzle, PuzzleParseError},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use glam::I64Vec2;
 use nom::{
     bytes::complete::tag,
-    character::complete::{i64, line_ending, newline},
-    multi::separated_list1,
-    sequence::{delimited, preceded, separated_pair, tuple},
-    IResult,
+    character::complete::i64,
+    sequence::{delimited, preceded, separated_pair},
+    IResult, Parser,
 };
 
-fn end_of_line(input: &str) -> IResult<&str, &str> {
-    if input.is_empty() {
-        Ok((input, input))
-    } else {
-        line_ending(input)
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
-struct Machine {
+pub struct Machine {
     a: I64Vec2,
     b: I64Vec2,
     prize: I64Vec2,
 }
 
 impl Machine {
+    /// The tokens for the only way to reach the prize, if it takes whole numbers of presses.
     fn cheapest_solution_cost(&self) -> Option<i64> {
-        let ax = self.a.x;
-        let ay = self.a.y;
-        let bx = self.b.x;
-        let by = self.b.y;
-        let px = self.prize.x;
-        let py = self.prize.y;
-
-        let b = (py * ax - px * ay) / (by * ax - bx * ay);
-        let a = (px - bx * b) / ax;
-        if ax * a + bx * b == px && ay * a + by * b == py {
-            Some(a * 3 + b)
-        } else {
-            None
-        }
+        let [a, b] = solve(
+            [[self.a.x, self.b.x], [self.a.y, self.b.y]],
+            [self.prize.x, self.prize.y],
+        )
+        .ok()?;
+        i64::try_from(a.to_integer()? * 3 + b.to_integer()?).ok()
     }
 }
 
 fn parse_machine(input: &str) -> IResult<&str, Machine> {
-    let (input, (a, b, prize)) = tuple((
+    let (input, (a, b, prize)) = (
         delimited(
             tag("Button A: "),
             separated_pair(preceded(tag("X+"), i64), tag(", Y+"), i64),
@@ -59,7 +48,8 @@ fn parse_machine(input: &str) -> IResult<&str, Machine> {
             separated_pair(preceded(tag("X="), i64), tag(", Y="), i64),
             end_of_line,
         ),
-    ))(input)?;
+    )
+        .parse(input)?;
 
     Ok((
         input,
@@ -72,12 +62,12 @@ fn parse_machine(input: &str) -> IResult<&str, Machine> {
 }
 
 #[aoc_generator(day13)]
-pub fn input_generator(input: &str) -> Vec<Machine> {
-    separated_list1(newline, parse_machine)(input).unwrap().1
+pub fn input_generator(input: &str) -> Result<Vec<Machine>, PuzzleParseError> {
+    parse_puzzle(13, input, lines(parse_machine))
 }
 
 #[aoc(day13, part1)]
-pub fn solve_part1(machines: &Vec<Machine>) -> i64 {
+pub fn solve_part1(machines: &[Machine]) -> i64 {
     machines
         .iter()
         .map(|m| m.cheapest_solution_cost().unwrap_or(0))
@@ -85,12 +75,11 @@ pub fn solve_part1(machines: &Vec<Machine>) -> i64 {
 }
 
 #[aoc(day13, part2)]
-pub fn solve_part2(machines: &Vec<Machine>) -> i64 {
-    let machines = machines.clone();
+pub fn solve_part2(machines: &[Machine]) -> i64 {
     machines
         .iter()
         .map(|m| {
-            let mut m = m.clone();
+            let mut m = *m;
             m.prize += I64Vec2::new(10000000000000, 10000000000000);
             m.cheapest_solution_cost().unwrap_or(0)
         })
@@ -121,11 +110,14 @@ Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279
 ";
-        let machines = input_generator(input);
-        dbg!(&machines);
-        dbg!(machines
-            .iter()
-            .map(|m| m.cheapest_solution_cost())
-            .collect_vec());
+        let machines = input_generator(input).unwrap();
+        assert_eq!(
+            machines
+                .iter()
+                .map(|m| m.cheapest_solution_cost())
+                .collect_vec(),
+            vec![Some(280), None, Some(200), None]
+        );
+        assert_eq!(solve_part1(&machines), 480);
     }
 }
@@ -1,49 +1,40 @@
-use core::time;
-use std::{fmt::Display, thread};
+use std::fmt::Display;
 
+use aoc_core::{
+    combinators::{ivec2, lines},
+    debug,
+    math::wrap,
+    parse::{parse_puzzle, PuzzleParseError},
+    player::Simulation,
+    render::{Canvas, Rgb},
+    trace,
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use glam::IVec2;
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
-    character::complete::{i32, line_ending, newline, space1},
-    multi::separated_list1,
+    character::complete::space1,
     sequence::{preceded, separated_pair},
-    IResult,
+    IResult, Parser,
 };
 
-fn end_of_line(input: &str) -> IResult<&str, &str> {
-    if input.is_empty() {
-        Ok((input, input))
-    } else {
-        line_ending(input)
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
-struct Robot {
+pub struct Robot {
     pos: IVec2,
     vel: IVec2,
 }
 
-fn parse_ivec2(input: &str) -> IResult<&str, IVec2> {
-    let (input, (x, y)) = separated_pair(i32, tag(","), i32)(input)?;
-    Ok((input, IVec2::new(x, y)))
-}
-
 fn parse_robot(input: &str) -> IResult<&str, Robot> {
     let (input, (pos, vel)) = separated_pair(
-        preceded(tag("p="), parse_ivec2),
+        preceded(tag("p="), ivec2),
         space1,
-        preceded(tag("v="), parse_ivec2),
-    )(input)?;
+        preceded(tag("v="), ivec2),
+    )
+    .parse(input)?;
     Ok((input, Robot { pos, vel }))
 }
 
-fn wrap(x: i32, min: i32, max: i32) -> i32 {
-    min + (x - min).rem_euclid(max - min + 1)
-}
-
 #[derive(Debug, Clone)]
 pub struct Map {
     size: IVec2,
@@ -51,7 +42,14 @@ pub struct Map {
 }
 
 impl Map {
-    fn step(&mut self, n: usize) {
+    pub fn new(robots: &[Robot]) -> Self {
+        Self {
+            size: IVec2::new(101, 103),
+            robots: robots.to_vec(),
+        }
+    }
+
+    fn advance(&mut self, n: usize) {
         for _ in 0..n {
             for robot in self.robots.iter_mut() {
                 robot.pos.x = wrap(robot.pos.x + robot.vel.x, 0, self.size.x - 1);
@@ -81,8 +79,8 @@ impl Map {
         self.robots
             .iter()
             .into_group_map_by(|r| r.pos.x)
-            .into_iter()
-            .map(|(_, robots)| {
+            .into_values()
+            .map(|robots| {
                 let mut largest_span = 0;
                 let mut current_span = 0;
                 let mut last_y = None;
@@ -91,7 +89,7 @@ impl Map {
                     .sorted_by(|a, b| a.pos.y.cmp(&b.pos.y))
                     .map(|r| r.pos.y)
                 {
-                    if last_y.is_some() && y == last_y.unwrap() + 1 {
+                    if last_y.is_some_and(|last_y| y == last_y + 1) {
                         current_span += 1;
                     } else {
                         current_span = 1;
@@ -112,6 +110,31 @@ impl Map {
     }
 }
 
+impl Simulation for Map {
+    fn step(&mut self) -> bool {
+        self.advance(1);
+        true
+    }
+
+    fn canvas(&self) -> Option<&dyn Canvas> {
+        Some(self)
+    }
+}
+
+impl Canvas for Map {
+    fn size(&self) -> IVec2 {
+        self.size
+    }
+
+    fn colour(&self, pos: IVec2) -> Rgb {
+        if self.robots.iter().any(|r| r.pos == pos) {
+            [40, 200, 80]
+        } else {
+            [10, 10, 30]
+        }
+    }
+}
+
 impl Display for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for y in 0..self.size.y {
@@ -136,43 +159,64 @@ impl Display for Map {
 }
 
 #[aoc_generator(day14)]
-pub fn input_generator(input: &str) -> Vec<Robot> {
-    separated_list1(newline, parse_robot)(input).unwrap().1
+pub fn input_generator(input: &str) -> Result<Vec<Robot>, PuzzleParseError> {
+    parse_puzzle(14, input, lines(parse_robot))
 }
 
 #[aoc(day14, part1)]
-pub fn solve_part1(machines: &Vec<Robot>) -> usize {
-    let mut map = Map {
-        size: IVec2::new(101, 103),
-        robots: machines.clone(),
-    };
+pub fn solve_part1(machines: &[Robot]) -> usize {
+    let mut map = Map::new(machines);
 
-    map.step(100);
+    map.advance(100);
 
+    debug!("quadrant counts: {:?}", map.quadrant_counts());
     map.safety_factor()
 }
 
+/// The first second the robots line up into a tree, if they ever do. Their positions repeat
+/// every `101 * 103` seconds, so there's no point looking further than that.
 #[aoc(day14, part2)]
-pub fn solve_part2(machines: &Vec<Robot>) -> usize {
-    let mut map = Map {
-        size: IVec2::new(101, 103),
-        robots: machines.clone(),
-    };
+pub fn solve_part2(machines: &[Robot]) -> Option<usize> {
+    let mut map = Map::new(machines);
 
-    let mut i = 0;
-    while !map.has_long_vertical_span() {
-        map.step(1);
-        i += 1;
+    for i in 0..(map.size.x * map.size.y) as usize {
+        if map.has_long_vertical_span() {
+            trace!("after {i} seconds:\n{map}");
+            return Some(i);
+        }
+        map.advance(1);
     }
 
-    println!("{}", map);
+    None
+}
+
+/// Random robots for stress testing.
+pub mod generate {
+    use aoc_core::rng::Rng;
 
-    i
+    /// `size` robots anywhere in the 101 by 103 space, each moving up to 99 tiles a second along
+    /// each axis. Nothing arranges them into a tree, so only part 1 is meaningful.
+    pub fn input(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        (0..size)
+            .map(|_| {
+                format!(
+                    "p={},{} v={},{}",
+                    rng.range(0..101),
+                    rng.range(0..103),
+                    rng.range(-99..100),
+                    rng.range(-99..100)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_core::assert_snapshot;
 
     #[test]
     fn test_day14_input_generator() {
@@ -188,12 +232,20 @@ p=9,3 v=2,3
 p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3";
-        let robots = input_generator(input);
+        let robots = input_generator(input).unwrap();
         let mut map = Map {
             size: IVec2::new(11, 7),
             robots,
         };
 
+        let mut frames = vec![map.to_string()];
+        let mut stepped = map.clone();
+        for _ in 0..5 {
+            stepped.step();
+            frames.push(stepped.to_string());
+        }
+        assert_snapshot!("day14_first_seconds", frames.join("\n\n"));
+
         assert_eq!(
             map.to_string(),
             "1.12.......
@@ -205,7 +257,7 @@ p=9,5 v=-3,-3";
 .......1..."
         );
 
-        map.step(100);
+        map.advance(100);
 
         assert_eq!(
             map.to_string(),
@@ -220,4 +272,22 @@ p=9,5 v=-3,-3";
 
         assert_eq!(map.safety_factor(), 12);
     }
+
+    #[test]
+    fn test_generated_input() {
+        for seed in 0..5 {
+            let robots = input_generator(&generate::input(500, seed)).unwrap();
+            assert_eq!(robots.len(), 500);
+            assert!(robots
+                .iter()
+                .all(|robot| robot.pos.x < 101 && robot.pos.y < 103));
+            solve_part1(&robots);
+        }
+    }
+
+    #[test]
+    fn test_part2_gives_up_without_a_tree() {
+        let robots = input_generator("p=0,4 v=3,-3\np=6,3 v=-1,-3").unwrap();
+        assert_eq!(solve_part2(&robots), None);
+    }
 }
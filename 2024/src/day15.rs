@@ -1,14 +1,16 @@
 use std::{collections::VecDeque, fmt::Display};
 
+use aoc_core::{
+    combinators::{char_rows, sections},
+    dir::Dir4,
+    grid::Grid,
+    parse::{parse_puzzle, PuzzleParseError},
+    player::Simulation,
+    render::{Canvas, Colour, Rgb},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use glam::IVec2;
-use nom::{
-    character::complete::{newline, one_of},
-    multi::{many1, separated_list1},
-    sequence::separated_pair,
-    IResult, Parser,
-};
-use vecgrid::Vecgrid;
+use nom::{IResult, Parser};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
@@ -18,14 +20,16 @@ enum Tile {
     Robot,
 }
 
-impl From<char> for Tile {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '.' => Tile::Empty,
-            '#' => Tile::Wall,
-            'O' => Tile::Box,
-            '@' => Tile::Robot,
-            _ => unreachable!(),
+            '.' => Ok(Tile::Empty),
+            '#' => Ok(Tile::Wall),
+            'O' => Ok(Tile::Box),
+            '@' => Ok(Tile::Robot),
+            c => Err(c),
         }
     }
 }
@@ -45,6 +49,17 @@ impl Display for Tile {
     }
 }
 
+impl Colour for Tile {
+    fn colour(&self) -> Rgb {
+        match self {
+            Tile::Empty => EMPTY_COLOUR,
+            Tile::Wall => WALL_COLOUR,
+            Tile::Box => BOX_COLOUR,
+            Tile::Robot => ROBOT_COLOUR,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile2 {
     Empty,
@@ -70,75 +85,45 @@ impl Display for Tile2 {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-impl From<Direction> for IVec2 {
-    fn from(direction: Direction) -> Self {
-        match direction {
-            Direction::Up => IVec2::NEG_Y,
-            Direction::Right => IVec2::X,
-            Direction::Down => IVec2::Y,
-            Direction::Left => IVec2::NEG_X,
-        }
-    }
-}
-
-impl From<char> for Direction {
-    fn from(c: char) -> Self {
-        match c {
-            '^' => Direction::Up,
-            '>' => Direction::Right,
-            'v' => Direction::Down,
-            '<' => Direction::Left,
-            _ => unreachable!(),
+impl Colour for Tile2 {
+    fn colour(&self) -> Rgb {
+        match self {
+            Tile2::Empty => EMPTY_COLOUR,
+            Tile2::Wall => WALL_COLOUR,
+            Tile2::BoxLeft | Tile2::BoxRight => BOX_COLOUR,
+            Tile2::Robot => ROBOT_COLOUR,
         }
     }
 }
 
-impl Display for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Direction::Up => "^",
-                Direction::Right => ">",
-                Direction::Down => "v",
-                Direction::Left => "<",
-            }
-        )
-    }
-}
+const EMPTY_COLOUR: Rgb = [20, 20, 20];
+const WALL_COLOUR: Rgb = [110, 110, 120];
+const BOX_COLOUR: Rgb = [200, 140, 60];
+const ROBOT_COLOUR: Rgb = [230, 50, 50];
 
 #[derive(Debug, Clone)]
 pub struct Game {
-    map: Vecgrid<Tile>,
+    map: Grid<Tile>,
     robot_pos: IVec2,
-    movements: VecDeque<Direction>,
-    last_move: Option<Direction>,
+    movements: VecDeque<Dir4>,
+    last_move: Option<Dir4>,
 }
 
 impl Game {
     fn get(&self, pos: IVec2) -> Option<Tile> {
-        self.map.get(pos.y as usize, pos.x as usize).copied()
+        self.map.get(pos).copied()
     }
 
     fn set(&mut self, pos: IVec2, tile: Tile) {
-        self.map.set(pos.y as usize, pos.x as usize, tile).unwrap();
+        self.map[pos] = tile;
     }
 
-    fn move_obstacle(&mut self, pos: IVec2, direction: Direction) -> bool {
+    fn move_obstacle(&mut self, pos: IVec2, direction: Dir4) -> bool {
         let Some(tile) = self.get(pos) else {
             return false;
         };
 
-        let new_pos: IVec2 = pos + IVec2::from(direction);
+        let new_pos: IVec2 = pos + direction;
 
         match self.get(new_pos) {
             Some(Tile::Empty) => {
@@ -146,28 +131,38 @@ impl Game {
                 self.set(new_pos, tile);
                 true
             }
-            Some(Tile::Box) => {
-                if self.move_obstacle(new_pos, direction) {
-                    self.set(pos, Tile::Empty);
-                    self.set(new_pos, tile);
-                    true
-                } else {
-                    false
-                }
+            Some(Tile::Box) if self.move_obstacle(new_pos, direction) => {
+                self.set(pos, Tile::Empty);
+                self.set(new_pos, tile);
+                true
             }
             _ => false,
         }
     }
 
-    fn move_robot(&mut self, direction: Direction) -> bool {
+    fn move_robot(&mut self, direction: Dir4) -> bool {
         if self.move_obstacle(self.robot_pos, direction) {
-            self.robot_pos += IVec2::from(direction);
+            self.robot_pos += direction;
             true
         } else {
             false
         }
     }
 
+    fn run(&mut self) {
+        while self.step() {}
+    }
+
+    fn box_positions(&self) -> Vec<IVec2> {
+        self.map
+            .iter()
+            .filter(|(_, &tile)| tile == Tile::Box)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
+
+impl Simulation for Game {
     fn step(&mut self) -> bool {
         let Some(direction) = self.movements.pop_front() else {
             return false;
@@ -177,56 +172,40 @@ impl Game {
         true
     }
 
-    fn run(&mut self) {
-        while self.step() {}
-    }
-
-    fn box_positions(&self) -> Vec<IVec2> {
-        self.map
-            .enumerate_column_major()
-            .filter(|(_, &tile)| tile == Tile::Box)
-            .map(|(pos, _)| IVec2::new(pos.0 as i32, pos.1 as i32))
-            .collect()
+    fn canvas(&self) -> Option<&dyn Canvas> {
+        Some(&self.map)
     }
 }
 
 impl Display for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.map.column_len() {
-            for x in 0..self.map.row_len() {
-                write!(f, "{}", self.map.get(y, x).unwrap_or(&Tile::Empty))?;
-            }
-            if y < self.map.column_len() - 1 {
-                writeln!(f)?;
-            }
-        }
-        Ok(())
+        write!(f, "{}", self.map)
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Game2 {
-    map: Vecgrid<Tile2>,
+    map: Grid<Tile2>,
     robot_pos: IVec2,
-    movements: VecDeque<Direction>,
-    last_move: Option<Direction>,
+    movements: VecDeque<Dir4>,
+    last_move: Option<Dir4>,
 }
 
 impl Game2 {
     fn get(&self, pos: IVec2) -> Option<Tile2> {
-        self.map.get(pos.y as usize, pos.x as usize).copied()
+        self.map.get(pos).copied()
     }
 
     fn set(&mut self, pos: IVec2, tile: Tile2) {
-        self.map.set(pos.y as usize, pos.x as usize, tile).unwrap();
+        self.map[pos] = tile;
     }
 
-    fn move_obstacle(&mut self, pos: IVec2, direction: Direction) -> bool {
+    fn move_obstacle(&mut self, pos: IVec2, direction: Dir4) -> bool {
         let Some(tile) = self.get(pos) else {
             return false;
         };
 
-        let new_pos: IVec2 = pos + IVec2::from(direction);
+        let new_pos: IVec2 = pos + direction;
         let tile_at_new_pos = self.get(new_pos);
 
         match tile_at_new_pos {
@@ -236,7 +215,7 @@ impl Game2 {
                 true
             }
             Some(Tile2::BoxLeft | Tile2::BoxRight) => match direction {
-                Direction::Left | Direction::Right => {
+                Dir4::W | Dir4::E => {
                     if self.move_obstacle(new_pos, direction) {
                         self.set(pos, Tile2::Empty);
                         self.set(new_pos, tile);
@@ -245,10 +224,10 @@ impl Game2 {
                         false
                     }
                 }
-                Direction::Up | Direction::Down => match tile_at_new_pos {
+                Dir4::N | Dir4::S => match tile_at_new_pos {
                     Some(Tile2::BoxLeft) => {
                         if self.move_obstacle(new_pos, direction)
-                            && self.move_obstacle(new_pos + IVec2::X, direction)
+                            && self.move_obstacle(new_pos + Dir4::E, direction)
                         {
                             self.set(pos, Tile2::Empty);
                             self.set(new_pos, tile);
@@ -259,7 +238,7 @@ impl Game2 {
                     }
                     Some(Tile2::BoxRight) => {
                         if self.move_obstacle(new_pos, direction)
-                            && self.move_obstacle(new_pos + IVec2::NEG_X, direction)
+                            && self.move_obstacle(new_pos + Dir4::W, direction)
                         {
                             self.set(pos, Tile2::Empty);
                             self.set(new_pos, tile);
@@ -275,10 +254,10 @@ impl Game2 {
         }
     }
 
-    fn move_robot(&mut self, direction: Direction) -> bool {
+    fn move_robot(&mut self, direction: Dir4) -> bool {
         let map_before_move = self.map.clone();
         if self.move_obstacle(self.robot_pos, direction) {
-            self.robot_pos += IVec2::from(direction);
+            self.robot_pos += direction;
             true
         } else {
             self.map = map_before_move;
@@ -286,6 +265,20 @@ impl Game2 {
         }
     }
 
+    fn run(&mut self) {
+        while self.step() {}
+    }
+
+    fn box_positions(&self) -> Vec<IVec2> {
+        self.map
+            .iter()
+            .filter(|(_, &tile)| tile == Tile2::BoxLeft)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
+
+impl Simulation for Game2 {
     fn step(&mut self) -> bool {
         let Some(direction) = self.movements.pop_front() else {
             return false;
@@ -295,89 +288,76 @@ impl Game2 {
         true
     }
 
-    fn run(&mut self) {
-        while self.step() {}
-    }
-
-    fn box_positions(&self) -> Vec<IVec2> {
-        self.map
-            .enumerate_column_major()
-            .filter(|(_, &tile)| tile == Tile2::BoxLeft)
-            .map(|(pos, _)| IVec2::new(pos.0 as i32, pos.1 as i32))
-            .collect()
+    fn canvas(&self) -> Option<&dyn Canvas> {
+        Some(&self.map)
     }
 }
 
 impl Display for Game2 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.map.column_len() {
-            for x in 0..self.map.row_len() {
-                write!(f, "{}", self.map.get(y, x).unwrap_or(&Tile2::Empty))?;
-            }
-            if y < self.map.column_len() - 1 {
-                writeln!(f)?;
-            }
-        }
-        Ok(())
+        write!(f, "{}", self.map)
     }
 }
 
-fn parse_map(input: &str) -> IResult<&str, Vecgrid<Tile>> {
-    let (input, rows) =
-        separated_list1(newline, many1(one_of(".#O@").map(|c| Tile::from(c))))(input)?;
-    Ok((input, Vecgrid::from_rows(rows).unwrap()))
+fn parse_map(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
+    char_rows(|c| Tile::try_from(c).ok()).parse(input)
+}
+
+fn parse_movements(input: &str) -> IResult<&str, VecDeque<Dir4>> {
+    char_rows(|c| Dir4::try_from(c).ok())
+        .map(|movements| movements.into_iter().flatten().collect())
+        .parse(input)
 }
 
-fn parse_movements(input: &str) -> IResult<&str, VecDeque<Direction>> {
-    separated_list1(newline, many1(one_of("^>v<").map(|c| Direction::from(c))))(input)
-        .map(|(input, movements)| (input, VecDeque::from_iter(movements.into_iter().flatten())))
+fn parse_map_part2(input: &str) -> IResult<&str, Vec<Vec<Tile2>>> {
+    char_rows(|c| {
+        Tile::try_from(c).ok().map(|tile| match tile {
+            Tile::Empty => [Tile2::Empty, Tile2::Empty],
+            Tile::Wall => [Tile2::Wall, Tile2::Wall],
+            Tile::Box => [Tile2::BoxLeft, Tile2::BoxRight],
+            Tile::Robot => [Tile2::Robot, Tile2::Empty],
+        })
+    })
+    .map(|rows| rows.into_iter().map(|row| row.concat()).collect())
+    .parse(input)
 }
 
-fn parse_map_part2(input: &str) -> IResult<&str, Vecgrid<Tile2>> {
-    let (input, rows) = separated_list1(
-        newline,
-        many1(one_of(".#O@").map(|c| match c {
-            '.' => vec![Tile2::Empty, Tile2::Empty],
-            '#' => vec![Tile2::Wall, Tile2::Wall],
-            'O' => vec![Tile2::BoxLeft, Tile2::BoxRight],
-            '@' => vec![Tile2::Robot, Tile2::Empty],
-            _ => unreachable!(),
-        }))
-        .map(|v| v.into_iter().flatten().collect()),
-    )(input)?;
-    Ok((input, Vecgrid::from_rows(rows).unwrap()))
+fn missing_robot(input: &str) -> PuzzleParseError {
+    PuzzleParseError::at(15, input, 0, "a map containing the robot '@'")
 }
 
 #[aoc_generator(day15, part1)]
-pub fn input_generator_part1(input: &str) -> Game {
-    separated_pair(parse_map, many1(newline), parse_movements)(input)
-        .map(|(_, (map, movements))| Game {
-            map: map.clone(),
-            robot_pos: map
-                .enumerate_column_major()
-                .find(|(_, &tile)| tile == Tile::Robot)
-                .map(|(pos, _)| IVec2::new(pos.1 as i32, pos.0 as i32))
-                .unwrap(),
-            movements,
-            last_move: None,
-        })
-        .unwrap()
+pub fn input_generator_part1(input: &str) -> Result<Game, PuzzleParseError> {
+    let (rows, movements) = parse_puzzle(15, input, sections(parse_map, parse_movements))?;
+    let map = Grid::from_rows(rows).ok_or_else(|| PuzzleParseError::ragged(15, input))?;
+
+    let robot_pos = map
+        .find(|&tile| tile == Tile::Robot)
+        .ok_or_else(|| missing_robot(input))?;
+
+    Ok(Game {
+        map,
+        robot_pos,
+        movements,
+        last_move: None,
+    })
 }
 
 #[aoc_generator(day15, part2)]
-pub fn input_generator_part2(input: &str) -> Game2 {
-    separated_pair(parse_map_part2, many1(newline), parse_movements)(input)
-        .map(|(_, (map, movements))| Game2 {
-            map: map.clone(),
-            robot_pos: map
-                .enumerate_column_major()
-                .find(|(_, &tile)| tile == Tile2::Robot)
-                .map(|(pos, _)| IVec2::new(pos.1 as i32, pos.0 as i32))
-                .unwrap(),
-            movements,
-            last_move: None,
-        })
-        .unwrap()
+pub fn input_generator_part2(input: &str) -> Result<Game2, PuzzleParseError> {
+    let (rows, movements) = parse_puzzle(15, input, sections(parse_map_part2, parse_movements))?;
+    let map = Grid::from_rows(rows).ok_or_else(|| PuzzleParseError::ragged(15, input))?;
+
+    let robot_pos = map
+        .find(|&tile| tile == Tile2::Robot)
+        .ok_or_else(|| missing_robot(input))?;
+
+    Ok(Game2 {
+        map,
+        robot_pos,
+        movements,
+        last_move: None,
+    })
 }
 
 #[aoc(day15, part1)]
@@ -387,7 +367,7 @@ pub fn solve_part1(game: &Game) -> i32 {
 
     game.box_positions()
         .iter()
-        .map(|pos| 100 * pos.x + pos.y)
+        .map(|pos| 100 * pos.y + pos.x)
         .sum()
 }
 
@@ -398,13 +378,14 @@ pub fn solve_part2(game: &Game2) -> i32 {
 
     game.box_positions()
         .iter()
-        .map(|pos| 100 * pos.x + pos.y)
+        .map(|pos| 100 * pos.y + pos.x)
         .sum()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_core::assert_snapshot;
 
     #[test]
     fn test_day15_input_generator() {
@@ -418,7 +399,7 @@ mod test {
 ########
 
 <^^>>>vv<v>>v<<";
-        let game = input_generator_part1(input);
+        let game = input_generator_part1(input).unwrap();
         assert_eq!(
             game.to_string(),
             "########
@@ -432,6 +413,27 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_day15_part1_steps() {
+        let input = "########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<";
+        let mut game = input_generator_part1(input).unwrap();
+
+        let mut frames = vec![game.to_string()];
+        while game.step() {
+            frames.push(game.to_string());
+        }
+        assert_snapshot!("day15_part1_steps", frames.join("\n\n"));
+    }
+
     #[test]
     fn test_day15_part1() {
         let input = "##########
@@ -455,9 +457,8 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
-        let mut game = input_generator_part1(input);
+        let mut game = input_generator_part1(input).unwrap();
         game.run();
-        println!("{}", game);
         assert_eq!(
             game.to_string(),
             "##########
@@ -484,8 +485,8 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 #######
 
 <vv<<^^<<^^";
-        let game = input_generator_part2(input);
-        println!("{}", game);
+        let game = input_generator_part2(input).unwrap();
+        assert_snapshot!("day15_part2_input", game);
     }
 
     #[test]
@@ -499,16 +500,23 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 #######
 
 <vv<<^^<<^^";
-        let mut game = input_generator_part2(input);
-
-        let n = game.movements.len();
-        for i in 0..=n {
-            println!("Second {}", i);
-            println!("{}", game);
-            game.step();
-            if i < n {
-                println!();
-            }
+        let mut game = input_generator_part2(input).unwrap();
+
+        let mut frames = vec![game.to_string()];
+        while game.step() {
+            frames.push(game.to_string());
         }
+        assert_snapshot!("day15_part2_steps", frames.join("\n\n"));
+
+        assert_eq!(
+            game.to_string(),
+            "##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############"
+        );
     }
 }
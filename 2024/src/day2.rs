@@ -1,21 +1,18 @@
-use aoc_runner_derive::{aoc, aoc_generator};
-use nom::{
-    character::complete::{i32, newline, space1},
-    multi::separated_list0,
+use aoc_core::{
+    combinators::{lines, space_separated},
+    parallel::prelude::*,
+    parse::{parse_puzzle, PuzzleParseError},
 };
+use aoc_runner_derive::{aoc, aoc_generator};
+use nom::character::complete::i32;
 
 #[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Vec<Vec<i32>> {
-    separated_list0(
-        newline::<&str, nom::error::Error<&str>>,
-        separated_list0(space1, i32),
-    )(input)
-    .map(|(_, result)| result)
-    .unwrap()
+pub fn input_generator(input: &str) -> Result<Vec<Vec<i32>>, PuzzleParseError> {
+    parse_puzzle(2, input, lines(space_separated(i32)))
 }
 
 #[aoc(day2, part1)]
-pub fn solve_part1(reports: &Vec<Vec<i32>>) -> usize {
+pub fn solve_part1(reports: &[Vec<i32>]) -> usize {
     reports
         .iter()
         .filter(|report| is_report_safe(report))
@@ -23,9 +20,9 @@ pub fn solve_part1(reports: &Vec<Vec<i32>>) -> usize {
 }
 
 #[aoc(day2, part2)]
-pub fn solve_part2(reports: &Vec<Vec<i32>>) -> usize {
+pub fn solve_part2(reports: &[Vec<i32>]) -> usize {
     reports
-        .iter()
+        .maybe_par_iter()
         .filter(|&report| {
             if is_report_safe(report) {
                 return true;
@@ -43,11 +40,39 @@ pub fn solve_part2(reports: &Vec<Vec<i32>>) -> usize {
         .count()
 }
 
-fn is_report_safe(report: &Vec<i32>) -> bool {
+fn is_report_safe(report: &[i32]) -> bool {
     let pair_diffs = report.windows(2).map(|w| w[0] - w[1]).collect::<Vec<i32>>();
-    let signum = pair_diffs[0].signum();
+    // A single level has no neighbours to be unsafe with.
+    let Some(signum) = pair_diffs.first().map(|diff| diff.signum()) else {
+        return true;
+    };
     pair_diffs.iter().all(|diff| {
         let dist = diff.abs();
-        diff.signum() == signum && dist >= 1 && dist <= 3
+        diff.signum() == signum && (1..=3).contains(&dist)
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day2() {
+        let input = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+        let input = input_generator(input).unwrap();
+        assert_eq!(solve_part1(&input), 2);
+        assert_eq!(solve_part2(&input), 4);
+    }
+
+    #[test]
+    fn test_single_level_reports() {
+        let input = input_generator("3\n5 9").unwrap();
+        assert_eq!(solve_part1(&input), 1);
+        assert_eq!(solve_part2(&input), 2);
+    }
+}
@@ -1,29 +1,30 @@
+use aoc_core::parse::{parse_puzzle, PuzzleParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{anychar, u32},
-    combinator::map,
+    combinator::{map, rest},
     multi::{many0, many_till},
-    sequence::{delimited, separated_pair},
+    sequence::{delimited, separated_pair, terminated},
     Parser,
 };
 
 fn parse_mul(input: &str) -> nom::IResult<&str, (u32, u32)> {
-    delimited(tag("mul("), separated_pair(u32, tag(","), u32), tag(")"))(input)
+    delimited(tag("mul("), separated_pair(u32, tag(","), u32), tag(")")).parse(input)
 }
 
 fn parse_all_mul(input: &str) -> nom::IResult<&str, Vec<(u32, u32)>> {
-    many0(many_till(anychar, parse_mul).map(|(_, mul)| mul))(input)
+    many0(many_till(anychar, parse_mul).map(|(_, mul)| mul)).parse(input)
 }
 
 #[aoc_generator(day3, part1)]
-pub fn input_generator_part1(input: &str) -> Vec<(u32, u32)> {
-    parse_all_mul(input).unwrap().1
+pub fn input_generator_part1(input: &str) -> Result<Vec<(u32, u32)>, PuzzleParseError> {
+    parse_puzzle(3, input, terminated(parse_all_mul, rest))
 }
 
 #[aoc(day3, part1)]
-pub fn solve_part1(input: &Vec<(u32, u32)>) -> u32 {
+pub fn solve_part1(input: &[(u32, u32)]) -> u32 {
     input.iter().map(|(x, y)| x * y).sum()
 }
 
@@ -45,7 +46,8 @@ fn parse_all_mul_with_conds(input: &str) -> nom::IResult<&str, Vec<Instruction>>
             )),
         )
         .map(|(_, i)| i),
-    )(input)
+    )
+    .parse(input)
 }
 
 #[aoc(day3, part2)]
@@ -135,4 +137,13 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_day3_examples() {
+        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        assert_eq!(solve_part1(&input_generator_part1(input).unwrap()), 161);
+
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        assert_eq!(solve_part2(input), 48);
+    }
 }
@@ -1,11 +1,14 @@
 use std::collections::{BTreeSet, HashMap};
 
+use aoc_core::{
+    combinators::{comma_separated, lines, sections},
+    parse::{parse_puzzle, PuzzleParseError},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 use nom::{
-    character::complete::{char, newline, u8},
-    multi::separated_list1,
-    sequence::{separated_pair, tuple},
+    character::complete::{char, u8},
+    sequence::separated_pair,
 };
 
 #[derive(Debug)]
@@ -15,22 +18,20 @@ pub struct SafetyManual {
 }
 
 #[aoc_generator(day5)]
-pub fn input_generator(input: &str) -> SafetyManual {
-    let (ordering_rules, updates) = separated_pair(
-        separated_list1(
-            newline::<&str, nom::error::Error<&str>>,
-            separated_pair(u8, char('|'), u8),
+pub fn input_generator(input: &str) -> Result<SafetyManual, PuzzleParseError> {
+    let (ordering_rules, updates) = parse_puzzle(
+        5,
+        input,
+        sections(
+            lines(separated_pair(u8, char('|'), u8)),
+            lines(comma_separated(u8)),
         ),
-        tuple((newline, newline)),
-        separated_list1(newline, separated_list1(char(','), u8)),
-    )(input)
-    .unwrap()
-    .1;
+    )?;
 
-    SafetyManual {
-        ordering_rules: BTreeSet::from_iter(ordering_rules.into_iter()),
+    Ok(SafetyManual {
+        ordering_rules: BTreeSet::from_iter(ordering_rules),
         updates,
-    }
+    })
 }
 
 #[aoc(day5, part1)]
@@ -48,8 +49,10 @@ pub fn solve_part1(manual: &SafetyManual) -> u32 {
         .sum()
 }
 
+/// The sum of the middle pages of the incorrectly ordered updates, once they're put in order.
+/// `None` if the rules don't settle which page of an update goes in the middle.
 #[aoc(day5, part2)]
-pub fn solve_part2(manual: &SafetyManual) -> u32 {
+pub fn solve_part2(manual: &SafetyManual) -> Option<u32> {
     manual
         .updates
         .iter()
@@ -72,12 +75,69 @@ pub fn solve_part2(manual: &SafetyManual) -> u32 {
                 }
             }
 
-            counts
+            // The middle page has as many pages ordered before it as after it, so there must be
+            // exactly one such page. Pages that no rule mentions have no count at all.
+            if update.len() % 2 == 0 {
+                return None;
+            }
+            update
                 .iter()
-                .find(|(&_, &count)| count == 0)
-                .unwrap()
-                .0
-                .clone() as u32
+                .filter(|page| counts.get(page).copied().unwrap_or(0) == 0)
+                .exactly_one()
+                .ok()
+                .map(|&page| page as u32)
         })
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day5() {
+        let input = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+        let input = input_generator(input).unwrap();
+        assert_eq!(solve_part1(&input), 143);
+        assert_eq!(solve_part2(&input), Some(123));
+    }
+
+    #[test]
+    fn test_part2_without_a_middle_page() {
+        // Found by `aoc fuzz`: no rule mentions either page, which used to panic.
+        let input = input_generator("7|6\n\n8,7").unwrap();
+        assert_eq!(solve_part2(&input), None);
+        // Both 1 and 3 go before 2, but nothing orders them against each other.
+        let input = input_generator("1|2\n3|2\n\n2,1,3").unwrap();
+        assert_eq!(solve_part2(&input), None);
+        let input = input_generator("1|2\n\n2,1").unwrap();
+        assert_eq!(solve_part2(&input), None);
+    }
+}
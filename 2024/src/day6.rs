@@ -1,116 +1,203 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
+use aoc_core::{
+    dir::Dir4,
+    grid::Grid,
+    parallel::prelude::*,
+    parse::PuzzleParseError,
+    player::Simulation,
+    render::{Canvas, Rgb},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use glam::IVec2;
-use nom::{
-    character::complete::{newline, one_of},
-    multi::{many1, separated_list1},
-};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Map {
-    size: IVec2,
-    obstructions: HashSet<IVec2>,
+    obstructions: Grid<bool>,
     guard: IVec2,
 }
 
 #[aoc_generator(day6)]
-pub fn input_generator(input: &str) -> Map {
-    let raw_map = separated_list1(
-        newline::<&str, nom::error::Error<&str>>,
-        many1(one_of("^#.")),
-    )(input)
-    .unwrap()
-    .1;
-
-    let height = raw_map.len();
-    let width = raw_map[0].len();
-
-    let mut obstructions = HashSet::new();
-    let mut guard = IVec2::ZERO;
-
-    for y in 0..height {
-        for x in 0..width {
-            if raw_map[y][x] == '#' {
-                obstructions.insert(IVec2::new(x as i32, y as i32));
-            }
-            if raw_map[y][x] == '^' {
-                guard = IVec2::new(x as i32, y as i32);
-            }
-        }
-    }
+pub fn input_generator(input: &str) -> Result<Map, PuzzleParseError> {
+    let raw_map = Grid::parse_with(6, input, |c| "^#.".contains(c).then_some(c))?;
+
+    let guard = raw_map
+        .find(|&c| c == '^')
+        .ok_or_else(|| PuzzleParseError::at(6, input, 0, "a map containing the guard '^'"))?;
 
-    Map {
-        size: IVec2::new(width as i32, height as i32),
-        obstructions,
+    Ok(Map {
+        obstructions: raw_map.map(|&c| c == '#'),
         guard,
-    }
+    })
+}
+
+/// The guard's patrol, one move or turn at a time.
+#[derive(Debug, Clone)]
+pub struct Patrol {
+    map: Map,
+    position: IVec2,
+    direction: Dir4,
+    visited: HashMap<IVec2, HashSet<Dir4>>,
+    looped: bool,
 }
 
-fn in_map(map: &Map, position: IVec2) -> bool {
-    position.x >= 0 && position.x < map.size.x && position.y >= 0 && position.y < map.size.y
+impl Patrol {
+    pub fn new(map: Map) -> Self {
+        Self {
+            position: map.guard,
+            direction: Dir4::N,
+            visited: HashMap::new(),
+            looped: false,
+            map,
+        }
+    }
 }
 
-fn get_visited(map: &Map) -> Option<HashMap<IVec2, HashSet<IVec2>>> {
-    let mut position = map.guard.clone();
-    let mut direction = IVec2::NEG_Y;
-    let mut visited: HashMap<IVec2, HashSet<IVec2>> = HashMap::new();
+impl Simulation for Patrol {
+    fn step(&mut self) -> bool {
+        if self.looped || !self.map.obstructions.contains(self.position) {
+            return false;
+        }
 
-    while in_map(map, position) {
-        if visited
-            .get(&position)
-            .map(|dirs| dirs.contains(&direction))
-            .unwrap_or(false)
+        if !self
+            .visited
+            .entry(self.position)
+            .or_default()
+            .insert(self.direction)
         {
             // Hit a loop
-            return None;
+            self.looped = true;
+            return false;
+        }
+
+        let in_front = self.position + self.direction;
+        if self.map.obstructions.get(in_front) == Some(&true) {
+            self.direction = self.direction.turn_right();
+        } else {
+            self.position = in_front;
         }
 
-        visited
-            .entry(position)
-            .or_insert_with(HashSet::new)
-            .insert(direction);
+        true
+    }
+
+    fn canvas(&self) -> Option<&dyn Canvas> {
+        Some(self)
+    }
+}
 
-        let in_front = position + direction;
-        if map.obstructions.contains(&in_front) {
-            direction = direction.perp();
-            continue;
+impl Canvas for Patrol {
+    fn size(&self) -> IVec2 {
+        self.map.obstructions.size()
+    }
+
+    fn colour(&self, pos: IVec2) -> Rgb {
+        if pos == self.position {
+            [230, 50, 50]
+        } else if self.map.obstructions[pos] {
+            [110, 110, 120]
+        } else if self.visited.contains_key(&pos) {
+            [240, 200, 60]
+        } else {
+            [20, 20, 20]
         }
+    }
+}
 
-        position += direction;
+impl Display for Patrol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.map.obstructions.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, &obstructed) in row.iter().enumerate() {
+                let pos = IVec2::new(x as i32, y as i32);
+                if pos == self.position {
+                    write!(f, "{}", self.direction)?;
+                } else if obstructed {
+                    write!(f, "#")?;
+                } else if self.visited.contains_key(&pos) {
+                    write!(f, "X")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+        }
+        Ok(())
     }
+}
 
-    Some(visited)
+fn get_visited(map: Map) -> Option<HashMap<IVec2, HashSet<Dir4>>> {
+    let mut patrol = Patrol::new(map);
+    while patrol.step() {}
+
+    (!patrol.looped).then_some(patrol.visited)
 }
 
 #[aoc(day6, part1)]
 pub fn solve_part1(map: &Map) -> usize {
-    get_visited(map).unwrap().len()
+    get_visited(map.clone()).unwrap().len()
 }
 
 #[aoc(day6, part2)]
 pub fn solve_part2(map: &Map) -> usize {
-    let mut visited = get_visited(map).unwrap();
+    let mut visited = get_visited(map.clone()).unwrap();
     visited.remove(&map.guard);
 
     visited
-        .iter()
+        .maybe_par_iter()
         .filter(|(&v, _)| {
             let mut new_obstructions = map.obstructions.clone();
-            new_obstructions.insert(v);
+            new_obstructions[v] = true;
             let new_map = Map {
-                size: map.size,
                 obstructions: new_obstructions,
                 guard: map.guard,
             };
 
-            get_visited(&new_map).is_none()
+            get_visited(new_map).is_none()
         })
         .count()
 }
 
+/// Random maps for stress testing.
+pub mod generate {
+    use aoc_core::rng::Rng;
+
+    use super::*;
+
+    /// A `size` by `size` map that the guard walks out of, with about one cell in fifteen
+    /// obstructed.
+    pub fn input(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        loop {
+            let guard = rng.below(size * size);
+            let input = (0..size)
+                .map(|y| {
+                    (0..size)
+                        .map(|x| match y * size + x {
+                            i if i == guard => '^',
+                            _ if rng.chance(1.0 / 15.0) => '#',
+                            _ => '.',
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            // Both parts need the unobstructed patrol to leave the map.
+            if get_visited(input_generator(&input).unwrap()).is_some() {
+                return input;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_day6() {
         let input = "....#.....
@@ -123,7 +210,17 @@ mod tests {
 ........#.
 #.........
 ......#...";
-        let map = super::input_generator(input);
-        dbg!(super::solve_part2(&map));
+        let map = super::input_generator(input).unwrap();
+        assert_eq!(super::solve_part1(&map), 41);
+        assert_eq!(super::solve_part2(&map), 6);
+    }
+
+    #[test]
+    fn test_generated_input() {
+        for seed in 0..5 {
+            let map = input_generator(&generate::input(20, seed)).unwrap();
+            assert!(solve_part1(&map) > 0);
+            solve_part2(&map);
+        }
     }
 }
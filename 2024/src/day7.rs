@@ -1,22 +1,18 @@
-use std::iter::once;
-
-use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::{repeat_n, Itertools};
-use nom::{
-    bytes::complete::tag,
-    character::complete::{i64, newline},
-    multi::separated_list1,
-    sequence::separated_pair,
+use aoc_core::{
+    combinators::{lines, space_separated},
+    parallel::prelude::*,
+    parse::{parse_puzzle, PuzzleParseError},
 };
+use aoc_runner_derive::{aoc, aoc_generator};
+use nom::{bytes::complete::tag, character::complete::i64, sequence::separated_pair};
 
 #[aoc_generator(day7)]
-pub fn input_generator(input: &str) -> Vec<(i64, Vec<i64>)> {
-    separated_list1(
-        newline::<&str, nom::error::Error<&str>>,
-        separated_pair(i64, tag(": "), separated_list1(tag(" "), i64)),
-    )(input)
-    .unwrap()
-    .1
+pub fn input_generator(input: &str) -> Result<Vec<(i64, Vec<i64>)>, PuzzleParseError> {
+    parse_puzzle(
+        7,
+        input,
+        lines(separated_pair(i64, tag(": "), space_separated(i64))),
+    )
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -26,18 +22,50 @@ enum Operator {
     Concat,
 }
 
+/// Whether `operands` can be combined left to right into `solution`. Works backwards from the last
+/// operand, so only operators that could have produced the remaining target are tried.
+fn is_solvable(solution: i64, operands: &[i64], valid_operators: &[Operator]) -> bool {
+    let Some((&last, rest)) = operands.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return solution == last;
+    }
+
+    valid_operators.iter().any(|operator| match operator {
+        Operator::Add => solution >= last && is_solvable(solution - last, rest, valid_operators),
+        Operator::Multiply => {
+            if last == 0 {
+                solution == 0
+            } else {
+                solution % last == 0 && is_solvable(solution / last, rest, valid_operators)
+            }
+        }
+        Operator::Concat => {
+            let shift = 10i64.pow(last.checked_ilog10().unwrap_or(0) + 1);
+            solution >= last
+                && (solution - last) % shift == 0
+                && is_solvable((solution - last) / shift, rest, valid_operators)
+        }
+    })
+}
+
+/// Reference for [`is_solvable`]: tries every arrangement of operators.
+#[cfg(test)]
 fn get_operators(
     solution: i64,
-    operands: &Vec<i64>,
+    operands: &[i64],
     valid_operators: Vec<Operator>,
 ) -> Option<Vec<Operator>> {
+    use itertools::{repeat_n, Itertools};
+
     let num_operators = operands.len() - 1;
     let possible_arrangements = repeat_n(valid_operators, num_operators).multi_cartesian_product();
 
     possible_arrangements.into_iter().find(|operators| {
         let result = operands
             .iter()
-            .zip(once(&Operator::Add).chain(operators.into_iter()))
+            .zip(std::iter::once(&Operator::Add).chain(operators.iter()))
             .fold(0, |acc, (operand, operator)| match operator {
                 Operator::Add => acc + operand,
                 Operator::Multiply => acc * operand,
@@ -49,24 +77,84 @@ fn get_operators(
 }
 
 #[aoc(day7, part1)]
-pub fn solve_part1(formulas: &Vec<(i64, Vec<i64>)>) -> i64 {
-    let valid_formulas = formulas.iter().filter(|(solution, operands)| {
-        get_operators(*solution, operands, vec![Operator::Add, Operator::Multiply]).is_some()
+pub fn solve_part1(formulas: &[(i64, Vec<i64>)]) -> i64 {
+    let valid_formulas = formulas.maybe_par_iter().filter(|(solution, operands)| {
+        is_solvable(*solution, operands, &[Operator::Add, Operator::Multiply])
     });
 
     valid_formulas.map(|(solution, _)| solution).sum()
 }
 
 #[aoc(day7, part2)]
-pub fn solve_part2(formulas: &Vec<(i64, Vec<i64>)>) -> i64 {
-    let valid_formulas = formulas.iter().filter(|(solution, operands)| {
-        get_operators(
+pub fn solve_part2(formulas: &[(i64, Vec<i64>)]) -> i64 {
+    let valid_formulas = formulas.maybe_par_iter().filter(|(solution, operands)| {
+        is_solvable(
             *solution,
             operands,
-            vec![Operator::Add, Operator::Multiply, Operator::Concat],
+            &[Operator::Add, Operator::Multiply, Operator::Concat],
         )
-        .is_some()
     });
 
     valid_formulas.map(|(solution, _)| solution).sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::differential::assert_agree;
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    #[test]
+    fn test_day7() {
+        let input = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+        let formulas = input_generator(input).unwrap();
+        assert_eq!(solve_part1(&formulas), 3749);
+        assert_eq!(solve_part2(&formulas), 11387);
+    }
+
+    /// Small equations whose target is either reachable by some arrangement of operators or
+    /// arbitrary.
+    fn equation() -> impl Strategy<Value = (i64, Vec<i64>)> {
+        vec(0..20i64, 1..6).prop_flat_map(|operands| {
+            let reachable = vec(0..3usize, operands.len() - 1).prop_map({
+                let operands = operands.clone();
+                move |choices| {
+                    operands[1..].iter().zip(choices).fold(
+                        operands[0],
+                        |acc, (&operand, choice)| match choice {
+                            0 => acc + operand,
+                            1 => acc * operand,
+                            _ => format!("{acc}{operand}").parse().unwrap(),
+                        },
+                    )
+                }
+            });
+            (prop_oneof![reachable, 0..10_000i64], Just(operands))
+        })
+    }
+
+    #[test]
+    fn test_is_solvable_matches_brute_force() {
+        for operators in [
+            vec![Operator::Add, Operator::Multiply],
+            vec![Operator::Add, Operator::Multiply, Operator::Concat],
+        ] {
+            assert_agree(
+                equation(),
+                |(solution, operands)| {
+                    get_operators(*solution, operands, operators.clone()).is_some()
+                },
+                |(solution, operands)| is_solvable(*solution, operands, &operators),
+            );
+        }
+    }
+}
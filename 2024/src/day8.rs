@@ -3,18 +3,17 @@ use std::{
     fmt::Display,
 };
 
+use aoc_core::{
+    combinators::char_rows,
+    debug,
+    geometry::ray,
+    parse::{end_of_line, parse_puzzle, PuzzleParseError},
+    trace,
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use glam::IVec2;
 use itertools::Itertools;
-use nom::{
-    character::{
-        complete::{line_ending, satisfy},
-        is_alphanumeric,
-    },
-    multi::many1,
-    sequence::terminated,
-    IResult,
-};
+use nom::sequence::terminated;
 
 #[derive(Debug, Clone)]
 pub struct Map {
@@ -41,23 +40,18 @@ impl Map {
     fn antenna_pair_antinodes(&self, a: &IVec2, b: &IVec2) -> HashSet<IVec2> {
         let mut antinodes = HashSet::new();
 
+        let x = a - (b - a);
+        let y = b - (a - b);
         if self.resonant_harmonics {
-            let d = b - a;
-            let mut curr = a.clone();
-            while self.in_bounds(&curr) {
-                antinodes.insert(curr);
-                curr -= d;
-            }
-
-            let d = a - b;
-            curr = b.clone();
-            while self.in_bounds(&curr) {
-                antinodes.insert(curr);
-                curr -= d;
+            // Every whole multiple of the distance between them, outwards from each antenna.
+            for (from, towards) in [(*a, x), (*b, y)] {
+                antinodes.extend(
+                    ray(from.as_i64vec2(), towards.as_i64vec2())
+                        .map(|p| p.as_ivec2())
+                        .take_while(|p| self.in_bounds(p)),
+                );
             }
         } else {
-            let x = a - (b - a);
-            let y = b - (a - b);
             if self.in_bounds(&x) {
                 antinodes.insert(x);
             }
@@ -106,31 +100,24 @@ impl Display for Map {
     }
 }
 
-fn end_of_line(input: &str) -> IResult<&str, &str> {
-    if input.is_empty() {
-        Ok((input, input))
-    } else {
-        line_ending(input)
-    }
-}
-
 #[aoc_generator(day8)]
-pub fn input_generator(input: &str) -> Map {
-    let raw_map = many1(terminated(
-        many1(satisfy(|c| c == '.' || is_alphanumeric(c as u8))),
-        end_of_line,
-    ))(input)
-    .unwrap()
-    .1;
+pub fn input_generator(input: &str) -> Result<Map, PuzzleParseError> {
+    let raw_map = parse_puzzle(
+        8,
+        input,
+        terminated(
+            char_rows(|c| (c == '.' || c.is_ascii_alphanumeric()).then_some(c)),
+            end_of_line,
+        ),
+    )?;
 
     let height = raw_map.len();
     let width = raw_map[0].len();
 
     let mut antennas: HashMap<char, Vec<IVec2>> = HashMap::new();
 
-    for y in 0..height {
-        for x in 0..width {
-            let c = raw_map[y][x];
+    for (y, row) in raw_map.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
             if c != '.' {
                 antennas
                     .entry(c)
@@ -140,18 +127,19 @@ pub fn input_generator(input: &str) -> Map {
         }
     }
 
-    Map {
+    Ok(Map {
         size: IVec2::new(width as i32, height as i32),
         antennas,
         resonant_harmonics: false,
-    }
+    })
 }
 
 #[aoc(day8, part1)]
 pub fn solve_part1(map: &Map) -> usize {
     let antinodes = map.get_antinodes();
 
-    println!("{}", map);
+    debug!("{} antenna frequencies", map.antennas.len());
+    trace!("{map}");
 
     antinodes.len()
 }
@@ -166,6 +154,7 @@ pub fn solve_part2(map: &Map) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_core::assert_snapshot;
 
     #[test]
     fn test_day8_part1() {
@@ -182,7 +171,7 @@ mod tests {
 ............
 ............";
 
-        let map = input_generator(&input);
+        let map = input_generator(input).unwrap();
 
         let output = format!("{}", map);
 
@@ -202,7 +191,8 @@ mod tests {
 ..........#."
         );
 
-        assert_eq!(solve_part1(&map), 9);
+        // The rendering above has 13 `#`s, and one more antinode under the top `A`.
+        assert_eq!(solve_part1(&map), 14);
     }
 
     #[test]
@@ -220,10 +210,22 @@ mod tests {
 ............
 ............";
 
-        let map = input_generator(&input);
+        let map = input_generator(input).unwrap();
 
         let output = solve_part2(&map);
 
         assert_eq!(output, 34);
+
+        let mut map = map;
+        map.resonant_harmonics = true;
+        assert_snapshot!("day8_part2", map);
+    }
+
+    #[test]
+    fn test_part2_steps_by_the_whole_distance() {
+        // The antennas are 4 apart, so the points 1 apart between and beyond them aren't
+        // antinodes.
+        let map = input_generator("..a...a.....").unwrap();
+        assert_eq!(solve_part2(&map), 3);
     }
 }
@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use aoc_core::{input::trim, parse::PuzzleParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::{repeat_n, Itertools};
 
@@ -22,7 +23,7 @@ impl Disk {
             .rev()
             .filter(|(_, b)| b.is_some())
         {
-            while blocks[i].is_some() && i < blocks.len() {
+            while i < blocks.len() && blocks[i].is_some() {
                 i += 1;
             }
 
@@ -39,6 +40,45 @@ impl Disk {
     pub fn compact_whole_files(&self) -> Disk {
         let mut blocks = self.blocks.clone();
 
+        // The (start, length) of every run of free blocks and of every file, left to right.
+        let mut free = Vec::new();
+        let mut files = Vec::new();
+        let mut start = 0;
+        for (block, run) in &self.blocks.iter().chunk_by(|b| **b) {
+            let len = run.count();
+            match block {
+                Some(_) => files.push((start, len)),
+                None => free.push((start, len)),
+            }
+            start += len;
+        }
+
+        // Files only ever move left, so the space they leave behind is never usable by the files
+        // still to be moved, which all start further left.
+        for &(file_start, file_len) in files.iter().rev() {
+            let Some(span) = free
+                .iter_mut()
+                .take_while(|(free_start, _)| *free_start < file_start)
+                .find(|(_, free_len)| *free_len >= file_len)
+            else {
+                continue;
+            };
+
+            for k in 0..file_len {
+                blocks.swap(span.0 + k, file_start + k);
+            }
+            span.0 += file_len;
+            span.1 -= file_len;
+        }
+
+        Disk { blocks }
+    }
+
+    /// Reference for [`Disk::compact_whole_files`]: scans from the start of the disk for every file.
+    #[cfg(test)]
+    fn compact_whole_files_naive(&self) -> Disk {
+        let mut blocks = self.blocks.clone();
+
         for (_, file_blocks) in &blocks
             .clone()
             .iter()
@@ -92,10 +132,19 @@ impl Display for Disk {
 }
 
 #[aoc_generator(day9)]
-pub fn input_generator(input: &str) -> Disk {
-    let blocks = input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as usize)
+pub fn input_generator(input: &str) -> Result<Disk, PuzzleParseError> {
+    let input = trim(input);
+    let sizes = input
+        .char_indices()
+        .map(|(offset, c)| {
+            c.to_digit(10)
+                .map(|n| n as usize)
+                .ok_or_else(|| PuzzleParseError::at(9, input, offset, "a digit"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let blocks = sizes
+        .into_iter()
         .enumerate()
         .flat_map(|(i, n)| {
             let block = if i % 2 == 0 { Some(i / 2) } else { None };
@@ -103,7 +152,7 @@ pub fn input_generator(input: &str) -> Disk {
         })
         .collect();
 
-    Disk { blocks }
+    Ok(Disk { blocks })
 }
 
 #[aoc(day9, part1)]
@@ -118,15 +167,39 @@ pub fn solve_part2(disk: &Disk) -> usize {
     compacted.checksum()
 }
 
+/// Random disk maps for stress testing.
+pub mod generate {
+    use aoc_core::rng::Rng;
+
+    /// A disk map `size` digits long. Files take one to nine blocks and the gaps between them
+    /// zero to nine.
+    pub fn input(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        (0..size)
+            .map(|i| {
+                let len = if i % 2 == 0 {
+                    rng.range(1..10)
+                } else {
+                    rng.range(0..10)
+                };
+                char::from_digit(len as u32, 10).unwrap()
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use aoc_core::{assert_snapshot, differential::assert_agree};
+    use proptest::{collection::vec, prelude::*};
+
     use super::*;
 
     #[test]
     fn test_day9_input_generator() {
         let input = "2333133121414131402";
 
-        let disk = input_generator(&input);
+        let disk = input_generator(input).unwrap();
 
         assert_eq!(
             format!("{}", disk),
@@ -138,20 +211,24 @@ mod tests {
     fn test_day9_compact() {
         let input = "2333133121414131402";
 
-        let disk = input_generator(&input);
+        let disk = input_generator(input).unwrap();
         let compacted = disk.compact();
         assert_eq!(
             format!("{}", compacted),
             "0099811188827773336446555566.............."
         );
         assert_eq!(compacted.checksum(), 1928);
+
+        // Found by `aoc fuzz`: a disk with no free space used to be read past its end.
+        assert_eq!(solve_part1(&input_generator("7").unwrap()), 0);
+        assert_eq!(solve_part1(&input_generator("302").unwrap()), 3 + 4);
     }
 
     #[test]
     fn test_day9_compact_whole_files() {
         let input = "2333133121414131402";
 
-        let disk = input_generator(&input);
+        let disk = input_generator(input).unwrap();
         let compacted = disk.compact_whole_files();
         assert_eq!(
             format!("{}", compacted),
@@ -159,4 +236,48 @@ mod tests {
         );
         assert_eq!(compacted.checksum(), 2858);
     }
+
+    #[test]
+    fn test_day9_compact_generated() {
+        let disk = input_generator(&generate::input(15, 7)).unwrap();
+        let renderings = [
+            disk.to_string(),
+            disk.compact().to_string(),
+            disk.compact_whole_files().to_string(),
+        ];
+        assert_snapshot!("day9_compact_generated", renderings.join("\n"));
+    }
+
+    #[test]
+    fn test_compact_whole_files_matches_naive() {
+        let disk_map = vec(0..10usize, 1..20).prop_map(|sizes| {
+            sizes
+                .iter()
+                .map(|size| size.to_string())
+                .collect::<String>()
+        });
+        assert_agree(
+            disk_map,
+            |input| {
+                input_generator(input)
+                    .unwrap()
+                    .compact_whole_files_naive()
+                    .blocks
+            },
+            |input| input_generator(input).unwrap().compact_whole_files().blocks,
+        );
+    }
+
+    #[test]
+    fn test_generated_input() {
+        for seed in 0..5 {
+            let input = generate::input(101, seed);
+            let disk = input_generator(&input).unwrap();
+            assert_eq!(
+                disk.compact_whole_files().blocks,
+                disk.compact_whole_files_naive().blocks
+            );
+            solve_part1(&disk);
+        }
+    }
 }
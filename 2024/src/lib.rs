@@ -1,3 +1,4 @@
+use aoc_core::{parse::PuzzleParseError, player::Simulation};
 use aoc_runner_derive::aoc_lib;
 
 mod day1;
@@ -17,3 +18,39 @@ mod day8;
 mod day9;
 
 aoc_lib! { year = 2024 }
+
+/// Builds the step-based simulation behind a day's part from its input, for days that have one.
+pub fn simulation(
+    day: u8,
+    part: u8,
+    input: &str,
+) -> Option<Result<Box<dyn Simulation>, PuzzleParseError>> {
+    fn boxed<S: Simulation + 'static>(
+        sim: Result<S, PuzzleParseError>,
+    ) -> Result<Box<dyn Simulation>, PuzzleParseError> {
+        sim.map(|sim| Box::new(sim) as Box<dyn Simulation>)
+    }
+
+    match (day, part) {
+        (6, _) => Some(boxed(day6::input_generator(input).map(day6::Patrol::new))),
+        (14, _) => Some(boxed(
+            day14::input_generator(input).map(|robots| day14::Map::new(&robots)),
+        )),
+        (15, 1) => Some(boxed(day15::input_generator_part1(input))),
+        (15, 2) => Some(boxed(day15::input_generator_part2(input))),
+        _ => None,
+    }
+}
+
+/// A random input for a day of `size` (a grid's side, or a number of lines or digits) from `seed`,
+/// for days that have a generator.
+pub fn generate_input(day: u8, size: usize, seed: u64) -> Option<String> {
+    match day {
+        6 => Some(day6::generate::input(size, seed)),
+        9 => Some(day9::generate::input(size, seed)),
+        10 => Some(day10::generate::input(size, seed)),
+        12 => Some(day12::generate::input(size, seed)),
+        14 => Some(day14::generate::input(size, seed)),
+        _ => None,
+    }
+}
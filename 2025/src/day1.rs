@@ -1,23 +1,20 @@
+use aoc_core::{
+    combinators::lines,
+    math::wrap,
+    parse::{PuzzleParseError, parse_puzzle},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use nom::{
-    Parser,
-    character::complete::{newline, one_of, u32},
-    multi::separated_list0,
+    character::complete::{one_of, u32},
     sequence::pair,
 };
 
 #[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> Vec<i32> {
-    separated_list0(
-        newline::<&str, nom::error::Error<&str>>,
-        pair(one_of("LR"), u32),
-    )
-    .parse(input)
-    .unwrap()
-    .1
-    .into_iter()
-    .map(|(l_or_r, n)| if l_or_r == 'L' { -(n as i32) } else { n as i32 })
-    .collect()
+pub fn input_generator(input: &str) -> Result<Vec<i32>, PuzzleParseError> {
+    Ok(parse_puzzle(1, input, lines(pair(one_of("LR"), u32)))?
+        .into_iter()
+        .map(|(l_or_r, n)| if l_or_r == 'L' { -(n as i32) } else { n as i32 })
+        .collect())
 }
 
 #[aoc(day1, part1)]
@@ -25,7 +22,7 @@ pub fn solve_part1(input: &Vec<i32>) -> u32 {
     let mut landed_on_zero = 0;
     let mut c = 50;
     for n in input {
-        c = (c + n) % 100;
+        c = wrap(c + n, 0, 99);
         if c == 0 {
             landed_on_zero += 1;
         }
@@ -37,26 +34,16 @@ pub fn solve_part1(input: &Vec<i32>) -> u32 {
 pub fn solve_part2(input: &[i32]) -> u32 {
     let mut pointed_at_zero = 0;
     let mut c = 50;
-    for mut n in input.iter().copied() {
-        if n.is_negative() {
-            pointed_at_zero += (n / 100).unsigned_abs();
-            n %= 100;
-            c = (c + n) % 100;
-            if c.is_negative() {
-                if c != n {
-                    pointed_at_zero += 1;
-                }
-                c += 100;
-            }
-            c %= 100;
-            if c == 0 {
-                pointed_at_zero += 1;
-            }
+    for &n in input {
+        // The dial passes through every position between c and c + n, excluding c itself, and
+        // points at zero once for every multiple of 100 among them.
+        let (first, last) = if n > 0 {
+            (c + 1, c + n)
         } else {
-            c += n;
-            pointed_at_zero += (c / 100) as u32;
-            c %= 100;
-        }
+            (c + n, c - 1)
+        };
+        pointed_at_zero += (last.div_euclid(100) - (first - 1).div_euclid(100)) as u32;
+        c = wrap(c + n, 0, 99);
     }
     pointed_at_zero
 }
@@ -77,7 +64,7 @@ L1
 L99
 R14
 L82";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         assert_eq!(solve_part2(&input), 6);
     }
 }
@@ -1,3 +1,9 @@
+use std::collections::VecDeque;
+
+use aoc_core::{
+    combinators::{comma_separated, lines},
+    parse::{PuzzleParseError, parse_puzzle},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use good_lp::{
     Expression, IntoAffineExpression as _, Solution as _, SolverModel, highs, variable, variables,
@@ -6,7 +12,7 @@ use itertools::Itertools;
 use nom::{
     Parser,
     bytes::complete::tag,
-    character::complete::{newline, one_of, usize},
+    character::complete::{one_of, usize},
     multi::{many1, separated_list1},
     sequence::delimited,
 };
@@ -19,7 +25,37 @@ struct Machine {
 }
 
 impl Machine {
-    fn min_sequence_for_light_diagram(&self) -> Vec<&Vec<usize>> {
+    /// The fewest button presses that light up the diagram, found by a breadth-first search over
+    /// the lights' states. Pressing a button twice undoes it, so each is pressed at most once.
+    fn min_presses_for_light_diagram(&self) -> Option<usize> {
+        let target = light_mask(self.light_diagram.iter().positions(|&on| on));
+        let buttons = self
+            .wiring_schematics
+            .iter()
+            .map(|schematic| light_mask(schematic.iter().copied()))
+            .collect_vec();
+
+        let mut seen = vec![false; 1 << self.light_diagram.len()];
+        seen[0] = true;
+        let mut queue = VecDeque::from([(0, 0)]);
+        while let Some((lights, presses)) = queue.pop_front() {
+            if lights == target {
+                return Some(presses);
+            }
+            for &button in &buttons {
+                let next = lights ^ button;
+                if !seen[next] {
+                    seen[next] = true;
+                    queue.push_back((next, presses + 1));
+                }
+            }
+        }
+        None
+    }
+
+    /// Reference for [`Machine::min_presses_for_light_diagram`]: tries every set of buttons.
+    #[cfg(test)]
+    fn min_presses_for_light_diagram_naive(&self) -> Option<usize> {
         self.wiring_schematics
             .iter()
             .powerset()
@@ -40,8 +76,8 @@ impl Machine {
                     None
                 }
             })
-            .min_by_key(|sequence| sequence.len())
-            .unwrap()
+            .map(|sequence| sequence.len())
+            .min()
     }
 
     fn min_sequence_for_joltage_requirements(&self) -> usize {
@@ -71,12 +107,13 @@ impl Machine {
     }
 }
 
+fn light_mask(lights: impl IntoIterator<Item = usize>) -> usize {
+    lights.into_iter().fold(0, |mask, light| mask | 1 << light)
+}
+
 #[aoc_generator(day10)]
-fn input_generator(input: &str) -> Vec<Machine> {
-    separated_list1(newline, parse_machine)
-        .parse(input)
-        .unwrap()
-        .1
+fn input_generator(input: &str) -> Result<Vec<Machine>, PuzzleParseError> {
+    parse_puzzle(10, input, lines(parse_machine))
 }
 
 fn parse_machine(input: &str) -> nom::IResult<&str, Machine> {
@@ -110,18 +147,18 @@ fn parse_wiring_schematics(input: &str) -> nom::IResult<&str, Vec<Vec<usize>>> {
 }
 
 fn parse_wiring_schematic(input: &str) -> nom::IResult<&str, Vec<usize>> {
-    delimited(tag("("), separated_list1(tag(","), usize), tag(")")).parse(input)
+    delimited(tag("("), comma_separated(usize), tag(")")).parse(input)
 }
 
 fn parse_joltage_requirements(input: &str) -> nom::IResult<&str, Vec<usize>> {
-    delimited(tag("{"), separated_list1(tag(","), usize), tag("}")).parse(input)
+    delimited(tag("{"), comma_separated(usize), tag("}")).parse(input)
 }
 
 #[aoc(day10, part1)]
 fn solve_part1(machines: &[Machine]) -> usize {
     machines
         .iter()
-        .map(|machine| machine.min_sequence_for_light_diagram().len())
+        .map(|machine| machine.min_presses_for_light_diagram().unwrap())
         .sum()
 }
 
@@ -135,6 +172,9 @@ fn solve_part2(machines: &[Machine]) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::differential::assert_agree;
+    use proptest::{collection::vec, prelude::*, sample::subsequence};
+
     use super::*;
 
     #[test]
@@ -142,7 +182,7 @@ mod tests {
         let input = r"[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
 [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let result = solve_part1(&input);
         assert_eq!(result, 7);
     }
@@ -150,7 +190,7 @@ mod tests {
     #[test]
     fn test_part1_longer() {
         let input = r"[#.#..#.##] (0,1,2,5,6,7,8) (1,4,6,7,8) (0,5,7) (0,1,2,6,7) (0,1,2,3,5,7,8) (0,1,5,7) (0,1,3,7,8) {138,150,10,13,17,127,25,155,38}";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let result = solve_part1(&input);
         assert_eq!(result, 5);
     }
@@ -160,8 +200,31 @@ mod tests {
         let input = r"[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
 [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let result = solve_part2(&input);
         assert_eq!(result, 33);
     }
+
+    /// Small machines with up to eight lights. Only the lights and buttons matter for part 1.
+    fn machine() -> impl Strategy<Value = Machine> {
+        (1..=8usize).prop_flat_map(|lights| {
+            let button = subsequence((0..lights).collect_vec(), 1..=lights);
+            (vec(any::<bool>(), lights), vec(button, 1..8)).prop_map(
+                |(light_diagram, wiring_schematics)| Machine {
+                    light_diagram,
+                    wiring_schematics,
+                    joltage_requirements: Vec::new(),
+                },
+            )
+        })
+    }
+
+    #[test]
+    fn test_min_presses_matches_powerset() {
+        assert_agree(
+            machine(),
+            Machine::min_presses_for_light_diagram_naive,
+            Machine::min_presses_for_light_diagram,
+        );
+    }
 }
@@ -1,33 +1,26 @@
 use std::collections::HashMap;
 
-use aoc_runner_derive::{aoc, aoc_generator};
-use nom::{
-    Parser,
-    bytes::complete::tag,
-    character::complete::{alpha1, newline},
-    multi::separated_list1,
-    sequence::separated_pair,
+use aoc_core::{
+    combinators::{lines, space_separated},
+    parse::{PuzzleParseError, parse_puzzle},
 };
+use aoc_runner_derive::{aoc, aoc_generator};
+use nom::{Parser, bytes::complete::tag, character::complete::alpha1, sequence::separated_pair};
 use pathfinding::prelude::count_paths;
 
 #[aoc_generator(day11)]
-fn input_generator(input: &str) -> HashMap<String, Vec<String>> {
-    separated_list1(
-        newline::<&str, nom::error::Error<&str>>,
-        separated_pair(
-            alpha1::<&str, nom::error::Error<&str>>.map(|s| s.to_string()),
+fn input_generator(input: &str) -> Result<HashMap<String, Vec<String>>, PuzzleParseError> {
+    Ok(parse_puzzle(
+        11,
+        input,
+        lines(separated_pair(
+            alpha1.map(|s: &str| s.to_string()),
             tag(": "),
-            separated_list1(
-                tag(" "),
-                alpha1::<&str, nom::error::Error<&str>>.map(|s| s.to_string()),
-            ),
-        ),
-    )
-    .parse(input)
-    .unwrap()
-    .1
+            space_separated(alpha1.map(|s: &str| s.to_string())),
+        )),
+    )?
     .into_iter()
-    .collect()
+    .collect())
 }
 
 #[aoc(day11, part1)]
@@ -56,6 +49,82 @@ fn solve_part2(devices: &HashMap<String, Vec<String>>) -> usize {
     )
 }
 
+/// Random device graphs for stress testing.
+pub mod generate {
+    use aoc_core::rng::Rng;
+
+    /// Most devices only ever feed forwards into one device, so that path counts grow slowly
+    /// with the size of the graph. This many branch out into two instead.
+    const BRANCHES: usize = 32;
+
+    /// How far forwards in the chain of devices an output can reach.
+    const REACH: usize = 4;
+
+    /// A graph of `size` devices, at least five, including `you`, `svr`, `fft` and `dac`, all
+    /// eventually leading to `out` without any cycles. Every path from `svr` passes through `fft`
+    /// then `dac`.
+    pub fn input(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let size = size.max(5);
+
+        let reserved = ["you", "out", "svr", "fft", "dac"];
+        let mut names = (0..)
+            .map(name)
+            .filter(|name| !reserved.contains(&name.as_str()))
+            .take(size)
+            .collect::<Vec<_>>();
+        let (fft, dac) = (size / 3, 2 * size / 3);
+        let you = loop {
+            let you = 1 + rng.below(size - 1);
+            if you != fft && you != dac {
+                break you;
+            }
+        };
+        for (i, name) in [(0, "svr"), (fft, "fft"), (dac, "dac"), (you, "you")] {
+            names[i] = name.to_string();
+        }
+
+        let mut branches = BRANCHES;
+        let mut lines = (0..size)
+            .map(|i| {
+                // Outputs can't skip past fft or dac, and off the end of the chain is out.
+                let limit = [fft, dac, size].into_iter().find(|&c| c > i).unwrap();
+                let next = |rng: &mut Rng| {
+                    let j = i + 1 + rng.below(REACH.min(limit - i));
+                    names.get(j).map_or("out", String::as_str)
+                };
+
+                let mut outputs = vec![next(&mut rng)];
+                if branches > 0 && rng.chance(0.05) {
+                    branches -= 1;
+                    outputs.push(next(&mut rng));
+                }
+                if rng.chance(0.1) {
+                    outputs.push("out");
+                }
+                outputs.sort();
+                outputs.dedup();
+
+                format!("{}: {}", names[i], outputs.join(" "))
+            })
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut lines);
+        lines.join("\n")
+    }
+
+    /// A distinct lowercase name for every index, at least three letters long.
+    fn name(mut i: usize) -> String {
+        let mut name = String::new();
+        loop {
+            name.push((b'a' + (i % 26) as u8) as char);
+            i /= 26;
+            if i == 0 && name.len() >= 3 {
+                return name;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,7 +141,7 @@ fff: out
 ggg: out
 hhh: ccc fff iii
 iii: out";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let result = solve_part1(&input);
         assert_eq!(result, 5);
     }
@@ -92,8 +161,18 @@ dac: fff
 fff: ggg hhh
 ggg: out
 hhh: out";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let result = solve_part2(&input);
         assert_eq!(result, 2);
     }
+
+    #[test]
+    fn test_generated_input() {
+        for seed in 0..5 {
+            let devices = input_generator(&generate::input(300, seed)).unwrap();
+            assert_eq!(devices.len(), 300);
+            assert!(solve_part1(&devices) > 0);
+            assert!(solve_part2(&devices) > 0);
+        }
+    }
 }
@@ -1,10 +1,16 @@
+use aoc_core::{
+    combinators::{char_rows, lines, space_separated},
+    debug,
+    parse::{PuzzleParseError, parse_puzzle},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 use nom::{
     Parser,
     bytes::complete::tag,
-    character::complete::{digit1, newline, one_of, usize},
-    multi::{many1, separated_list1},
+    character::complete::{digit1, line_ending, usize},
+    combinator::map_res,
+    multi::many1,
     sequence::{delimited, separated_pair},
 };
 
@@ -12,19 +18,22 @@ type Shape = [bool; 9];
 type Region = ((usize, usize), Vec<usize>);
 
 #[aoc_generator(day12)]
-fn input_generator(input: &str) -> (Vec<Shape>, Vec<Region>) {
-    (many1(parse_shape), separated_list1(newline, parse_region))
-        .parse(input)
-        .unwrap()
-        .1
+fn input_generator(input: &str) -> Result<(Vec<Shape>, Vec<Region>), PuzzleParseError> {
+    parse_puzzle(12, input, (many1(parse_shape), lines(parse_region)))
 }
 
 fn parse_shape(input: &str) -> nom::IResult<&str, Shape> {
     delimited(
-        (digit1, tag(":"), newline),
-        separated_list1(newline, many1(one_of(".#").map(|c| c == '#')))
-            .map(|v| v.into_iter().flatten().collect_vec().try_into().unwrap()),
-        (newline, newline),
+        (digit1, tag(":"), line_ending),
+        map_res(
+            char_rows(|c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            }),
+            |v| Shape::try_from(v.into_iter().flatten().collect_vec()),
+        ),
+        (line_ending, line_ending),
     )
     .parse(input)
 }
@@ -33,20 +42,169 @@ fn parse_region(input: &str) -> nom::IResult<&str, Region> {
     separated_pair(
         separated_pair(usize, tag("x"), usize),
         tag(": "),
-        separated_list1(tag(" "), usize),
+        space_separated(usize),
     )
     .parse(input)
 }
 
+/// Whether the region's presents fit, by trying to pack them only when neither a count of cells
+/// nor a grid of whole 3x3 slots settles it. The real input is always settled without packing.
+/// A packing that isn't found within [`PACKING_STEPS`] counts as not fitting, so a region is
+/// only ever counted when it's known to be valid.
 fn is_region_valid(region: &Region, shapes: &[Shape]) -> bool {
-    let available_area = region.0.0 * region.0.1;
-    region
-        .1
+    let &((width, height), ref counts) = region;
+    let needed = counts
+        .iter()
+        .zip(shapes)
+        .map(|(n, shape)| shape.iter().filter(|&&b| b).count() * n)
+        .sum::<usize>();
+    if needed > width * height {
+        return false;
+    }
+    if (width / 3) * (height / 3) >= counts.iter().sum() {
+        return true;
+    }
+
+    let placements = shapes
         .iter()
-        .enumerate()
-        .map(|(i, n)| shapes[i].iter().filter(|b| **b).count() * n)
-        .sum::<usize>()
-        < available_area
+        .map(|shape| placements(shape, width, height))
+        .collect_vec();
+    let mut steps = PACKING_STEPS;
+    let packed = pack(
+        Cells::new(width * height),
+        width * height - needed,
+        &mut counts.clone(),
+        &placements,
+        &mut steps,
+    );
+    if steps == 0 {
+        debug!("gave up packing a {width}x{height} region");
+    }
+    packed
+}
+
+/// How many steps to spend packing a single region before giving up on it.
+const PACKING_STEPS: usize = 1 << 20;
+
+/// The cells of a region, one bit each, with the bits past its end already set.
+#[derive(Clone)]
+struct Cells(Vec<u64>);
+
+impl Cells {
+    fn new(len: usize) -> Self {
+        let mut words = vec![0; len / 64 + 1];
+        words[len / 64] = u64::MAX << (len % 64);
+        Cells(words)
+    }
+
+    fn contains(&self, cell: usize) -> bool {
+        self.0[cell / 64] & 1 << (cell % 64) != 0
+    }
+
+    fn insert(&mut self, cell: usize) {
+        self.0[cell / 64] |= 1 << (cell % 64);
+    }
+
+    /// The lowest cell not in the set, if any.
+    fn first_missing(&self) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|&word| word != u64::MAX)
+            .map(|i| i * 64 + (!self.0[i]).trailing_zeros() as usize)
+    }
+
+    /// How many cells are in neither set.
+    fn count_missing_from_both(&self, other: &Cells) -> usize {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (!a & !b).count_ones() as usize)
+            .sum()
+    }
+}
+
+/// Every way to put a shape in the region, rotated or reflected, as the cells it covers in
+/// ascending order.
+fn placements(shape: &Shape, width: usize, height: usize) -> Vec<Vec<usize>> {
+    // Reflected for the last four, then turned a quarter that many times.
+    let orient = |orientation: usize, (x, y): (usize, usize)| {
+        let reflected = if orientation >= 4 { (2 - x, y) } else { (x, y) };
+        (0..orientation % 4).fold(reflected, |(x, y), _| (2 - y, x))
+    };
+    let mut placements = Vec::new();
+    for orientation in 0..8 {
+        for (left, top) in (0..height.saturating_sub(2))
+            .flat_map(|y| (0..width.saturating_sub(2)).map(move |x| (x, y)))
+        {
+            let cells = (0..9)
+                .filter(|&i| shape[i])
+                .map(|i| orient(orientation, (i % 3, i / 3)))
+                .map(|(x, y)| (top + y) * width + left + x)
+                .sorted_unstable()
+                .collect_vec();
+            placements.push(cells);
+        }
+    }
+    placements.sort_unstable();
+    placements.dedup();
+    placements
+}
+
+/// Places the remaining presents without overlapping `filled`, leaving at most `slack` cells
+/// empty. Each step covers or gives up the lowest cell that's still free.
+fn pack(
+    mut filled: Cells,
+    slack: usize,
+    counts: &mut [usize],
+    placements: &[Vec<Vec<usize>>],
+    steps: &mut usize,
+) -> bool {
+    if counts.iter().all(|&n| n == 0) {
+        return true;
+    }
+    if *steps == 0 {
+        return false;
+    }
+    *steps -= 1;
+    let fits = |cells: &[usize], filled: &Cells| !cells.iter().any(|&cell| filled.contains(cell));
+
+    // Cells that no remaining present can still cover will have to be left empty.
+    let mut reachable = Cells(vec![0; filled.0.len()]);
+    for cells in (0..counts.len())
+        .filter(|&shape| counts[shape] > 0)
+        .flat_map(|shape| &placements[shape])
+        .filter(|cells| fits(cells, &filled))
+    {
+        cells.iter().for_each(|&cell| reachable.insert(cell));
+    }
+    let Some(at) = filled.first_missing() else {
+        return false;
+    };
+    if filled.count_missing_from_both(&reachable) > slack {
+        return false;
+    }
+
+    for shape in 0..counts.len() {
+        if counts[shape] == 0 {
+            continue;
+        }
+        for cells in &placements[shape] {
+            // Only placements whose lowest cell is `at`, so each packing is found once.
+            if cells[0] != at || !fits(cells, &filled) {
+                continue;
+            }
+            let mut packed = filled.clone();
+            cells.iter().for_each(|&cell| packed.insert(cell));
+            counts[shape] -= 1;
+            let found = pack(packed, slack, counts, placements, steps);
+            counts[shape] += 1;
+            if found {
+                return true;
+            }
+        }
+    }
+    filled.insert(at);
+    slack > 0 && pack(filled, slack - 1, counts, placements, steps)
 }
 
 #[aoc(day12, part1)]
@@ -96,8 +254,16 @@ mod tests {
 4x4: 0 0 0 0 2 0
 12x5: 1 0 1 0 2 2
 12x5: 1 0 1 0 3 2";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let result = solve_part1(&input);
         assert_eq!(result, 2);
     }
+
+    #[test]
+    fn test_part1_packs_regions_past_128_cells() {
+        let shapes = input_generator("0:\n###\n##.\n##.\n\n0x0: 0").unwrap().0;
+        // 132 cells, but only 12 whole 3x3 slots for 13 presents.
+        assert!(is_region_valid(&((12, 11), vec![13]), &shapes));
+        assert!(is_region_valid(&((12, 11), vec![18]), &shapes));
+    }
 }
@@ -1,75 +1,80 @@
-use std::ops::Range;
+use std::ops::RangeInclusive;
 
-use aoc_runner_derive::{aoc, aoc_generator};
-use nom::{
-    Parser, bytes::complete::tag, character::complete::u64, multi::separated_list0,
-    sequence::separated_pair,
+use aoc_core::{
+    combinators::{comma_separated, inclusive_range},
+    interval::IntervalSet,
+    parallel::prelude::*,
+    parse::{PuzzleParseError, parse_puzzle},
 };
+use aoc_runner_derive::{aoc, aoc_generator};
+use nom::character::complete::u64;
 
 #[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Vec<Range<u64>> {
-    separated_list0(
-        tag(","),
-        separated_pair(
-            u64::<&str, nom::error::Error<&str>>,
-            tag("-"),
-            u64::<&str, nom::error::Error<&str>>,
-        )
-        .map(|(start, end)| Range {
-            start,
-            end: end + 1,
-        }),
-    )
-    .parse(input)
-    .unwrap()
-    .1
+pub fn input_generator(input: &str) -> Result<IntervalSet<u64>, PuzzleParseError> {
+    parse_puzzle(2, input, comma_separated(inclusive_range(u64)))
+        .map(|ranges| ranges.into_iter().collect())
 }
 
+/// The sum of the IDs in the set that are a sequence of digits repeated exactly twice. An ID in
+/// two overlapping ranges is only one ID, so it counts once.
 #[aoc(day2, part1)]
-pub fn solve_part1(input: &[Range<u64>]) -> u64 {
-    let mut invalid_ids = Vec::new();
-    for range in input {
-        for id in range.clone() {
-            if !is_valid_id(id) {
-                invalid_ids.push(id);
-            }
-        }
-    }
-    invalid_ids.iter().sum()
+pub fn solve_part1(ids: &IntervalSet<u64>) -> u128 {
+    ids.ranges()
+        .maybe_par_iter()
+        .map(|range| {
+            digit_counts(range)
+                .filter(|len| len.is_multiple_of(2))
+                .map(|len| sum_repeating(range, len, len / 2))
+                .sum::<u128>()
+        })
+        .sum()
 }
 
-fn is_valid_id(id: u64) -> bool {
-    let s = id.to_string();
-    let (left, right) = s.split_at(s.len() / 2);
-    left != right
+/// The sum of the IDs in the set that are a sequence of digits repeated at least twice.
+#[aoc(day2, part2)]
+pub fn solve_part2(ids: &IntervalSet<u64>) -> u128 {
+    ids.ranges()
+        .maybe_par_iter()
+        .map(|range| {
+            digit_counts(range)
+                .flat_map(|len| {
+                    (1..len)
+                        .filter(move |&period| len.is_multiple_of(period))
+                        .map(move |period| sum_primitive(range, len, period))
+                })
+                .sum::<u128>()
+        })
+        .sum()
 }
 
-#[aoc(day2, part2)]
-pub fn solve_part2(input: &[Range<u64>]) -> u64 {
-    let mut invalid_ids = Vec::new();
-    for range in input {
-        for id in range.clone() {
-            if !is_valid_id_part2(id) {
-                invalid_ids.push(id);
-            }
-        }
-    }
-    invalid_ids.iter().sum()
+/// How many digits the IDs in the range can have.
+fn digit_counts(range: &RangeInclusive<u64>) -> RangeInclusive<u32> {
+    let digits = |id: u64| id.checked_ilog10().unwrap_or(0) + 1;
+    digits(*range.start())..=digits(*range.end())
 }
 
-fn is_valid_id_part2(id: u64) -> bool {
-    let s = id.to_string();
-    let len = s.len();
-    for n in 1..=len / 2 {
-        if !len.is_multiple_of(n) {
-            continue;
-        }
-        let sub = s.chars().take(n).collect::<String>();
-        if sub.repeat(len / n) == s {
-            return false;
-        }
+/// The sum of the `len` digit IDs in the range that repeat every `period` digits, found by
+/// multiplying each `period` digit pattern by a repunit such as 10101.
+fn sum_repeating(range: &RangeInclusive<u64>, len: u32, period: u32) -> u128 {
+    let repunit = (10u128.pow(len) - 1) / (10u128.pow(period) - 1);
+    let lo = (*range.start() as u128)
+        .div_ceil(repunit)
+        .max(10u128.pow(period - 1));
+    let hi = (*range.end() as u128 / repunit).min(10u128.pow(period) - 1);
+    if lo > hi {
+        return 0;
     }
-    true
+    repunit * (lo + hi) * (hi - lo + 1) / 2
+}
+
+/// Like [`sum_repeating`], but only the IDs that don't also repeat at a shorter period, so that
+/// each ID is counted once across the periods that divide `len`.
+fn sum_primitive(range: &RangeInclusive<u64>, len: u32, period: u32) -> u128 {
+    sum_repeating(range, len, period)
+        - (1..period)
+            .filter(|&shorter| period.is_multiple_of(shorter))
+            .map(|shorter| sum_primitive(range, len, shorter))
+            .sum::<u128>()
 }
 
 #[cfg(test)]
@@ -79,14 +84,45 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = r"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         assert_eq!(solve_part1(&input), 1227775554);
     }
 
     #[test]
     fn test_part2() {
         let input = r"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         assert_eq!(solve_part2(&input), 4174379265);
     }
+
+    #[test]
+    fn test_overlapping_ranges_count_each_id_once() {
+        let input = input_generator("11-22,20-33").unwrap();
+        assert_eq!(solve_part1(&input), 11 + 22 + 33);
+        let input = input_generator("11-111,100-222").unwrap();
+        assert_eq!(
+            solve_part2(&input),
+            (1..=9).map(|d| d * 11).sum::<u128>() + 111 + 222
+        );
+    }
+
+    #[test]
+    fn test_wide_ranges() {
+        let input = input_generator("68234-8291651").unwrap();
+        let brute = |repeats: fn(&str) -> bool| {
+            (68234..=8291651u64)
+                .filter(|id| repeats(&id.to_string()))
+                .map(u128::from)
+                .sum::<u128>()
+        };
+        assert_eq!(
+            solve_part1(&input),
+            brute(|s| s.len().is_multiple_of(2) && s[..s.len() / 2] == s[s.len() / 2..])
+        );
+        assert_eq!(
+            solve_part2(&input),
+            brute(|s| (1..s.len())
+                .any(|n| s.len().is_multiple_of(n) && s[..n].repeat(s.len() / n) == s))
+        );
+    }
 }
@@ -1,30 +1,23 @@
-use aoc_runner_derive::{aoc, aoc_generator};
-use nom::{
-    Parser,
-    character::complete::{self, satisfy},
-    combinator::map,
-    multi::{many1, separated_list0},
+use aoc_core::{
+    combinators::char_rows,
+    parse::{PuzzleParseError, parse_puzzle},
 };
+use aoc_runner_derive::{aoc, aoc_generator};
 
 #[aoc_generator(day3)]
-pub fn input_generator(input: &str) -> Vec<Vec<u32>> {
-    separated_list0(complete::newline, many1(single_digit))
-        .parse(input)
-        .unwrap()
-        .1
-}
-
-fn single_digit(input: &str) -> nom::IResult<&str, u32> {
-    map(satisfy(|c| c.is_ascii_digit()), |c| c.to_digit(10).unwrap()).parse(input)
+pub fn input_generator(input: &str) -> Result<Vec<Vec<u32>>, PuzzleParseError> {
+    parse_puzzle(3, input, char_rows(|c| c.to_digit(10)))
 }
 
 fn concat(digits: &[u32]) -> u64 {
     digits.iter().fold(0, |acc, elem| acc * 10 + *elem as u64)
 }
 
-fn get_joltage(bank: &[u32], num_batteries: usize) -> u64 {
+/// The largest joltage from turning on exactly `num_batteries` of the bank, or `None` if it doesn't
+/// have that many.
+fn get_joltage(bank: &[u32], num_batteries: usize) -> Option<u64> {
     let mut batteries = vec![0u32; num_batteries];
-    for i in 0..bank.len() - num_batteries + 1 {
+    for i in 0..=bank.len().checked_sub(num_batteries)? {
         for j in 0..num_batteries {
             let num = bank[i + j];
             if num > batteries[j] {
@@ -34,16 +27,16 @@ fn get_joltage(bank: &[u32], num_batteries: usize) -> u64 {
         }
     }
 
-    concat(&batteries)
+    Some(concat(&batteries))
 }
 
 #[aoc(day3, part1)]
-pub fn solve_part1(input: &[Vec<u32>]) -> u64 {
+pub fn solve_part1(input: &[Vec<u32>]) -> Option<u64> {
     input.iter().map(|bank| get_joltage(bank, 2)).sum()
 }
 
 #[aoc(day3, part2)]
-pub fn solve_part2(input: &[Vec<u32>]) -> u64 {
+pub fn solve_part2(input: &[Vec<u32>]) -> Option<u64> {
     input.iter().map(|bank| get_joltage(bank, 12)).sum()
 }
 
@@ -57,7 +50,14 @@ mod tests {
 811111111111119
 234234234234278
 818181911112111";
-        let input = input_generator(input);
-        assert_eq!(solve_part1(&input), 357);
+        let input = input_generator(input).unwrap();
+        assert_eq!(solve_part1(&input), Some(357));
+    }
+
+    #[test]
+    fn test_bank_too_short() {
+        let input = input_generator("4").unwrap();
+        assert_eq!(solve_part1(&input), None);
+        assert_eq!(solve_part2(&input), None);
     }
 }
@@ -1,60 +1,104 @@
-use aoc_runner_derive::{aoc, aoc_generator};
-use nom::{
-    Parser,
-    character::complete::{self, one_of},
-    multi::{many1, separated_list0},
-};
+use std::fmt::{self, Display};
 
-type Grid = gridit::Grid<bool>;
+use aoc_core::{
+    grid::Grid,
+    parse::PuzzleParseError,
+    player::Simulation,
+    render::{Canvas, Rgb},
+};
+use aoc_runner_derive::{aoc, aoc_generator};
+use glam::IVec2;
 
 #[aoc_generator(day4)]
-pub fn input_generator(input: &str) -> Grid {
-    let rows = separated_list0(
-        complete::newline::<&str, nom::error::Error<&str>>,
-        many1(one_of(".@").map(|c| c == '@')),
-    )
-    .parse(input)
-    .unwrap()
-    .1;
-
-    let width = rows[0].len();
-    let height = rows.len();
-    let items = rows.iter().flatten().cloned().collect::<Vec<_>>();
-    Grid::from(items, width, height)
+pub fn input_generator(input: &str) -> Result<Grid<bool>, PuzzleParseError> {
+    Grid::parse_with(4, input, |c| match c {
+        '@' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
 }
 
-fn removable_positions(grid: &Grid) -> Vec<gridit::Position> {
-    grid.positions()
-        .filter(|&p| {
-            matches!(grid.get(p), Some(true)) && grid.neighbors(p).filter(|&&n| n).count() < 4
-        })
+fn removable_positions(grid: &Grid<bool>) -> Vec<IVec2> {
+    grid.iter()
+        .filter(|&(p, &roll)| roll && grid.neighbors8(p).filter(|&(_, &n)| n).count() < 4)
+        .map(|(p, _)| p)
         .collect()
 }
 
-#[aoc(day4, part1)]
-pub fn solve_part1(grid: &Grid) -> usize {
-    removable_positions(grid).len()
+/// Removes every accessible roll at once on each step.
+#[derive(Debug, Clone)]
+pub struct Removal {
+    grid: Grid<bool>,
+    last_removed: Vec<IVec2>,
+    removed: usize,
 }
 
-#[aoc(day4, part2)]
-pub fn solve_part2(grid: &Grid) -> usize {
-    let mut total = 0;
-    let (width, height) = grid.size();
-    let items = grid.iter().cloned().collect::<Vec<_>>();
-    let mut grid = Grid::from(items, width, height);
+impl Removal {
+    pub fn new(grid: Grid<bool>) -> Self {
+        Self {
+            grid,
+            last_removed: Vec::new(),
+            removed: 0,
+        }
+    }
+}
 
-    loop {
-        let positions = removable_positions(&grid);
+impl Simulation for Removal {
+    fn step(&mut self) -> bool {
+        let positions = removable_positions(&self.grid);
         if positions.is_empty() {
-            break;
+            return false;
+        }
+
+        for &position in positions.iter() {
+            self.grid[position] = false;
+        }
+        self.removed += positions.len();
+        self.last_removed = positions;
+        true
+    }
+
+    fn canvas(&self) -> Option<&dyn Canvas> {
+        Some(self)
+    }
+}
+
+impl Canvas for Removal {
+    fn size(&self) -> IVec2 {
+        self.grid.size()
+    }
+
+    fn colour(&self, pos: IVec2) -> Rgb {
+        if self.grid[pos] {
+            [240, 240, 240]
+        } else if self.last_removed.contains(&pos) {
+            [230, 50, 50]
+        } else {
+            [20, 20, 20]
         }
+    }
+}
 
-        for position in positions.iter() {
-            grid.set(*position, false);
+impl Display for Removal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut cells = self.grid.map(|&roll| if roll { '@' } else { '.' });
+        for &position in &self.last_removed {
+            cells[position] = 'x';
         }
-        total += positions.len();
+        write!(f, "{cells}")
     }
-    total
+}
+
+#[aoc(day4, part1)]
+pub fn solve_part1(grid: &Grid<bool>) -> usize {
+    removable_positions(grid).len()
+}
+
+#[aoc(day4, part2)]
+pub fn solve_part2(grid: &Grid<bool>) -> usize {
+    let mut removal = Removal::new(grid.clone());
+    while removal.step() {}
+    removal.removed
 }
 
 #[cfg(test)]
@@ -73,7 +117,7 @@ mod tests {
 @.@@@.@@@@
 .@@@@@@@@.
 @.@.@@@.@.";
-        let grid = input_generator(input);
+        let grid = input_generator(input).unwrap();
         assert_eq!(solve_part1(&grid), 13);
     }
 }
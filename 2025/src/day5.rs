@@ -1,54 +1,48 @@
 use std::ops::RangeInclusive;
 
-use aoc_runner_derive::{aoc, aoc_generator};
-use itertools::Itertools;
-use nom::{
-    Parser,
-    bytes::complete::tag,
-    character::complete::{self, u64},
-    multi::separated_list0,
-    sequence::separated_pair,
+use aoc_core::{
+    combinators::{inclusive_range, lines, sections},
+    interval::IntervalSet,
+    parse::{PuzzleParseError, parse_puzzle},
 };
+use aoc_runner_derive::{aoc, aoc_generator};
+use nom::character::complete::u64;
 
 #[aoc_generator(day5)]
-pub fn input_generator(input: &str) -> (Vec<RangeInclusive<u64>>, Vec<u64>) {
-    separated_pair(
-        separated_list0(
-            complete::newline::<&str, nom::error::Error<&str>>,
-            inclusive_range,
-        ),
-        (complete::newline, complete::newline),
-        separated_list0(complete::newline::<&str, nom::error::Error<&str>>, u64),
-    )
-    .parse(input)
-    .unwrap()
-    .1
-}
-
-fn inclusive_range(input: &str) -> nom::IResult<&str, RangeInclusive<u64>> {
-    separated_pair(u64, tag("-"), u64)
-        .parse(input)
-        .map(|(input, (start, end))| (input, start..=end))
+pub fn input_generator(
+    input: &str,
+) -> Result<(Vec<RangeInclusive<u64>>, Vec<u64>), PuzzleParseError> {
+    parse_puzzle(5, input, sections(lines(inclusive_range(u64)), lines(u64)))
 }
 
 #[aoc(day5, part1)]
 pub fn solve_part1((ranges, numbers): &(Vec<RangeInclusive<u64>>, Vec<u64>)) -> usize {
+    let fresh: IntervalSet<u64> = ranges.iter().cloned().collect();
     numbers
         .iter()
-        .filter(|&number| ranges.iter().any(|range| range.contains(number)))
+        .filter(|&&number| fresh.contains(number))
         .count()
 }
 
 #[aoc(day5, part2)]
 pub fn solve_part2((ranges, _): &(Vec<RangeInclusive<u64>>, Vec<u64>)) -> usize {
+    let fresh: IntervalSet<u64> = ranges.iter().cloned().collect();
+    fresh.len() as usize
+}
+
+/// Reference for [`solve_part2`]: merges each range into the first merged range it overlaps.
+#[cfg(test)]
+fn solve_part2_naive((ranges, _): &(Vec<RangeInclusive<u64>>, Vec<u64>)) -> usize {
+    use itertools::Itertools;
+
     ranges
         .iter()
         .sorted_by(|a, b| a.start().cmp(b.start()))
         .fold(Vec::<RangeInclusive<u64>>::new(), |mut acc, range| {
             let mut combined = false;
             for r in acc.iter_mut() {
-                if r.overlaps(range) {
-                    *r = r.combine(range);
+                if r.start() <= range.end() && range.start() <= r.end() {
+                    *r = *r.start().min(range.start())..=*r.end().max(range.end());
                     combined = true;
                     break;
                 }
@@ -65,23 +59,11 @@ pub fn solve_part2((ranges, _): &(Vec<RangeInclusive<u64>>, Vec<u64>)) -> usize
         .sum()
 }
 
-trait RangeExt {
-    fn overlaps(&self, other: &RangeInclusive<u64>) -> bool;
-    fn combine(&self, other: &RangeInclusive<u64>) -> RangeInclusive<u64>;
-}
-
-impl RangeExt for RangeInclusive<u64> {
-    fn overlaps(&self, other: &RangeInclusive<u64>) -> bool {
-        self.start() <= other.end() && other.start() <= self.end()
-    }
-
-    fn combine(&self, other: &RangeInclusive<u64>) -> RangeInclusive<u64> {
-        *self.start().min(other.start())..=*self.end().max(other.end())
-    }
-}
-
 #[cfg(test)]
 mod tests {
+    use aoc_core::differential::assert_agree;
+    use proptest::{collection::vec, prelude::*};
+
     use super::*;
 
     #[test]
@@ -97,7 +79,7 @@ mod tests {
 11
 17
 32";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         assert_eq!(solve_part1(&input), 3);
     }
 
@@ -114,7 +96,20 @@ mod tests {
 11
 17
 32";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         assert_eq!(solve_part2(&input), 14);
     }
+
+    #[test]
+    fn test_part2_matches_naive() {
+        let ranges = vec(
+            (0..50u64, 0..10u64).prop_map(|(start, len)| start..=start + len),
+            0..8,
+        );
+        assert_agree(
+            ranges.prop_map(|ranges| (ranges, Vec::new())),
+            solve_part2_naive,
+            solve_part2,
+        );
+    }
 }
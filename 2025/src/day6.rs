@@ -1,8 +1,12 @@
+use aoc_core::{
+    combinators::{lines, space_separated},
+    input::trim,
+    parse::{PuzzleParseError, parse_puzzle},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
 use nom::{
     Parser,
     character::complete::{self, one_of, u64},
-    multi::separated_list1,
     sequence::{preceded, separated_pair, terminated},
 };
 
@@ -28,34 +32,42 @@ impl Problem {
 }
 
 #[aoc_generator(day6, part1)]
-fn input_generator_part_1(input: &str) -> Vec<Problem> {
-    let (nums, ops) = separated_pair(
-        separated_list1(
-            complete::newline::<&str, nom::error::Error<&str>>,
-            terminated(
-                preceded(complete::space0, separated_list1(complete::space1, u64)),
+fn input_generator_part_1(input: &str) -> Result<Vec<Problem>, PuzzleParseError> {
+    let (nums, ops) = parse_puzzle(
+        6,
+        input,
+        separated_pair(
+            lines(terminated(
+                preceded(complete::space0, space_separated(u64)),
                 complete::space0,
-            ),
-        ),
-        complete::newline,
-        terminated(
-            preceded(
-                complete::space0,
-                separated_list1(
-                    complete::space1,
-                    one_of("+*").map(|op| match op {
+            )),
+            complete::line_ending,
+            terminated(
+                preceded(
+                    complete::space0,
+                    space_separated(one_of("+*").map(|op| match op {
                         '+' => Operator::Add,
                         '*' => Operator::Multiply,
                         _ => unreachable!(),
-                    }),
+                    })),
                 ),
+                complete::space0,
             ),
-            complete::space0,
         ),
-    )
-    .parse(input)
-    .unwrap()
-    .1;
+    )?;
+
+    if let Some((line, _)) = input
+        .lines()
+        .zip(&nums)
+        .find(|(_, nums)| nums.len() != ops.len())
+    {
+        return Err(PuzzleParseError::at_span(
+            6,
+            input,
+            line,
+            format!("{} numbers, one per operator", ops.len()),
+        ));
+    }
 
     let mut problems = Vec::new();
     for i in 0..ops.len() {
@@ -65,16 +77,25 @@ fn input_generator_part_1(input: &str) -> Vec<Problem> {
         });
     }
 
-    problems
+    Ok(problems)
 }
 
 #[aoc_generator(day6, part2)]
-fn input_generator_part_2(input: &str) -> Vec<Problem> {
-    let lines = input
-        .lines()
-        .map(|line| line.to_string())
-        .collect::<Vec<_>>();
-    let (op_line, num_lines) = lines.split_last().unwrap();
+fn input_generator_part_2(input: &str) -> Result<Vec<Problem>, PuzzleParseError> {
+    let input = trim(input);
+    let lines = input.lines().collect::<Vec<_>>();
+    let Some((op_line, num_lines)) = lines.split_last() else {
+        return Err(PuzzleParseError::at(6, input, 0, "a line of operators"));
+    };
+    let width = op_line.chars().count();
+    if let Some(line) = num_lines.iter().find(|line| line.chars().count() < width) {
+        return Err(PuzzleParseError::at_span(
+            6,
+            input,
+            &line[line.len()..],
+            format!("a line {width} characters wide"),
+        ));
+    }
 
     let mut problems = Vec::new();
     let mut p = Problem {
@@ -109,7 +130,7 @@ fn input_generator_part_2(input: &str) -> Vec<Problem> {
         problems.push(p.clone());
     }
 
-    problems
+    Ok(problems)
 }
 
 #[aoc(day6, part1)]
@@ -132,7 +153,7 @@ mod tests {
  45 64  387 23 
   6 98  215 314
 *   +   *   +  ";
-        let input = input_generator_part_1(input);
+        let input = input_generator_part_1(input).unwrap();
         assert_eq!(solve_part1(&input), 4277556);
     }
 
@@ -142,7 +163,7 @@ mod tests {
  45 64  387 23 
   6 98  215 314
 *   +   *   +  ";
-        let input = input_generator_part_2(input);
+        let input = input_generator_part_2(input).unwrap();
         assert_eq!(solve_part2(&input), 3263827);
     }
 }
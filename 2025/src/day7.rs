@@ -1,33 +1,35 @@
-use std::collections::HashSet;
-
+use aoc_core::{grid::Grid, parse::PuzzleParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
-use glam::{UVec2, uvec2};
+use glam::IVec2;
 use pathfinding::prelude::{bfs_reach, count_paths};
 
 #[derive(Debug, Clone)]
 struct Manifold {
-    size: UVec2,
-    start: UVec2,
-    splitters: HashSet<UVec2>,
+    start: IVec2,
+    splitters: Grid<bool>,
 }
 
 impl Manifold {
+    fn is_splitter(&self, pos: IVec2) -> bool {
+        self.splitters.get(pos) == Some(&true)
+    }
+
     fn times_split(&self) -> usize {
         bfs_reach(self.start, |&pos| {
-            let beams = if self.splitters.contains(&pos) {
-                vec![pos + UVec2::X, pos - UVec2::X]
+            let beams = if self.is_splitter(pos) {
+                vec![pos + IVec2::X, pos - IVec2::X]
             } else {
                 vec![pos]
             };
 
             beams.into_iter().filter_map(|beam| {
-                let mut next = beam + UVec2::Y;
+                let mut next = beam + IVec2::Y;
 
-                while next.y < self.size.y {
-                    if self.splitters.contains(&next) {
+                while next.y < self.splitters.size().y {
+                    if self.is_splitter(next) {
                         return Some(next);
                     }
-                    next += UVec2::Y;
+                    next += IVec2::Y;
                 }
 
                 None
@@ -41,44 +43,28 @@ impl Manifold {
         count_paths(
             self.start,
             |&pos| {
-                let next = pos + UVec2::Y;
-                if self.splitters.contains(&next) {
-                    vec![pos + UVec2::X, pos - UVec2::X].into_iter()
+                let next = pos + IVec2::Y;
+                if self.is_splitter(next) {
+                    vec![pos + IVec2::X, pos - IVec2::X].into_iter()
                 } else {
                     vec![next].into_iter()
                 }
             },
-            |&pos| pos.y == self.size.y,
+            |&pos| pos.y == self.splitters.size().y,
         )
     }
 }
 
 #[aoc_generator(day7)]
-fn input_generator(input: &str) -> Manifold {
-    let width = input.lines().next().unwrap().len() as u32;
-    let height = input.lines().count() as u32;
-
-    let mut start = None;
-    let mut splitters = HashSet::new();
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            match c {
-                'S' => {
-                    start = Some(uvec2(x as u32, y as u32));
-                }
-                '^' => {
-                    splitters.insert(uvec2(x as u32, y as u32));
-                }
-                _ => (),
-            }
-        }
-    }
+fn input_generator(input: &str) -> Result<Manifold, PuzzleParseError> {
+    let map = Grid::parse_with(7, input, |c| "S^.".contains(c).then_some(c))?;
 
-    Manifold {
-        size: UVec2::new(width, height),
-        start: start.expect("No start position found"),
-        splitters,
-    }
+    Ok(Manifold {
+        start: map
+            .find(|&c| c == 'S')
+            .ok_or_else(|| PuzzleParseError::at(7, input, 0, "a start position 'S'"))?,
+        splitters: map.map(|&c| c == '^'),
+    })
 }
 
 #[aoc(day7, part1)]
@@ -113,7 +99,7 @@ mod tests {
 ...............
 .^.^.^.^.^...^.
 ...............";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         assert_eq!(solve_part1(&input), 21);
     }
 
@@ -135,7 +121,7 @@ mod tests {
 ...............
 .^.^.^.^.^...^.
 ...............";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         assert_eq!(solve_part2(&input), 40);
     }
 }
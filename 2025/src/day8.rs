@@ -1,26 +1,18 @@
+use aoc_core::{
+    combinators::{i64vec3, lines},
+    parse::{PuzzleParseError, parse_puzzle},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
-use glam::{I64Vec3, i64vec3};
+use glam::I64Vec3;
 use itertools::Itertools;
-use nom::{
-    Parser,
-    bytes::complete::tag,
-    character::complete::{i64, newline},
-    multi::{separated_list0, separated_list1},
-};
 use petgraph::{
     algo::{connected_components, kosaraju_scc},
     graph::UnGraph,
 };
 
 #[aoc_generator(day8)]
-pub fn input_generator(input: &str) -> Vec<I64Vec3> {
-    separated_list0(newline, parse_vec3).parse(input).unwrap().1
-}
-
-fn parse_vec3(input: &str) -> nom::IResult<&str, I64Vec3> {
-    separated_list1(tag(","), i64)
-        .parse(input)
-        .map(|(s, vec)| (s, i64vec3(vec[0], vec[1], vec[2])))
+pub fn input_generator(input: &str) -> Result<Vec<I64Vec3>, PuzzleParseError> {
+    parse_puzzle(8, input, lines(i64vec3))
 }
 
 #[aoc(day8, part1)]
@@ -68,6 +60,27 @@ fn circuits(graph: &UnGraph<I64Vec3, ()>) -> Vec<Vec<I64Vec3>> {
         .collect()
 }
 
+/// Random junction boxes for stress testing.
+pub mod generate {
+    use std::collections::HashSet;
+
+    use aoc_core::rng::Rng;
+
+    /// `size` distinct junction boxes spread through a cube 100,000 units across.
+    pub fn input(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let mut seen = HashSet::new();
+        let mut lines = Vec::with_capacity(size);
+        while lines.len() < size {
+            let point = [0; 3].map(|_| rng.range(0..100_000));
+            if seen.insert(point) {
+                lines.push(format!("{},{},{}", point[0], point[1], point[2]));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,10 +107,20 @@ mod tests {
 862,61,35
 984,92,344
 425,690,689";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let (graph, _) = circuit_graph(&input, 10);
         let circuits = circuits(&graph);
         let result: usize = circuits.iter().map(|c| c.len()).take(3).product();
         assert_eq!(result, 40);
     }
+
+    #[test]
+    fn test_generated_input() {
+        for seed in 0..3 {
+            let points = input_generator(&generate::input(60, seed)).unwrap();
+            assert_eq!(points.iter().unique().count(), 60);
+            assert!(solve_part1(&points) > 0);
+            solve_part2(&points);
+        }
+    }
 }
@@ -1,68 +1,72 @@
+use aoc_core::{
+    combinators::{i64vec2, lines},
+    geometry::{Polygon, Rect},
+    parse::{PuzzleParseError, parse_puzzle},
+};
 use aoc_runner_derive::{aoc, aoc_generator};
-use glam::{I64Vec2, i64vec2};
+use glam::I64Vec2;
 use itertools::Itertools;
-use nom::{
-    Parser,
-    bytes::complete::tag,
-    character::complete::{i64, newline},
-    multi::separated_list0,
-    sequence::separated_pair,
-};
 
 #[aoc_generator(day9)]
-pub fn input_generator(input: &str) -> Vec<I64Vec2> {
-    separated_list0(newline, parse_vec2).parse(input).unwrap().1
-}
-
-fn parse_vec2(input: &str) -> nom::IResult<&str, I64Vec2> {
-    separated_pair(i64, tag(","), i64)
-        .parse(input)
-        .map(|(s, (x, y))| (s, i64vec2(x, y)))
+pub fn input_generator(input: &str) -> Result<Vec<I64Vec2>, PuzzleParseError> {
+    parse_puzzle(9, input, lines(i64vec2))
 }
 
+/// The largest rectangle with red tiles at opposite corners, or `None` with fewer than two.
 #[aoc(day9, part1)]
-pub fn solve_part1(points: &[I64Vec2]) -> i64 {
+pub fn solve_part1(points: &[I64Vec2]) -> Option<i64> {
     points
         .iter()
         .tuple_combinations()
-        .map(|(a, b)| (a, b, ((b.x - a.x).abs() + 1) * ((b.y - a.y).abs() + 1)))
-        .sorted_by_key(|(_, _, distance)| *distance)
-        .next_back()
-        .unwrap()
-        .2
+        .map(|(&a, &b)| Rect::spanning(a, b).area())
+        .max()
 }
 
 #[aoc(day9, part2)]
-pub fn solve_part2(points: &[I64Vec2]) -> i64 {
-    let lines: Vec<(&I64Vec2, &I64Vec2)> = points.iter().circular_tuple_windows().collect_vec();
+pub fn solve_part2(points: &[I64Vec2]) -> Option<i64> {
+    let edges = Polygon::new(points.to_vec()).edges().collect_vec();
 
+    // The loop is made of horizontal and vertical lines, so a rectangle lies within it unless
+    // one of them cuts into it.
     points
         .iter()
         .tuple_combinations()
-        .filter(|(ba, bb)| {
-            !lines
+        .map(|(&a, &b)| Rect::spanning(a, b))
+        .filter(|rect| {
+            !edges
                 .iter()
-                .any(|(la, lb)| line_intersects_box(ba, bb, la, lb))
+                .any(|edge| rect.crosses_interior(&edge.bounds()))
         })
-        .map(|(a, b)| (a, b, ((b.x - a.x).abs() + 1) * ((b.y - a.y).abs() + 1)))
-        .sorted_by_key(|(_, _, distance)| *distance)
-        .next_back()
-        .unwrap()
-        .2
+        .map(|rect| rect.area())
+        .max()
 }
 
-fn line_intersects_box(ba: &I64Vec2, bb: &I64Vec2, la: &I64Vec2, lb: &I64Vec2) -> bool {
-    let bxmin = ba.x.min(bb.x);
-    let bxmax = ba.x.max(bb.x);
-    let bymin = ba.y.min(bb.y);
-    let bymax = ba.y.max(bb.y);
+/// Random floors for stress testing.
+pub mod generate {
+    use aoc_core::rng::Rng;
 
-    let lxmin = la.x.min(lb.x);
-    let lxmax = la.x.max(lb.x);
-    let lymin = la.y.min(lb.y);
-    let lymax = la.y.max(lb.y);
+    /// The outline of `size` side-by-side columns of red and green tiles with different heights,
+    /// standing on a common base. The outline is a closed loop of horizontal and vertical lines
+    /// like the real input, with `2 * size + 2` red tiles in order around it.
+    pub fn input(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let step = (100_000 / (size as i64 + 1)).max(2);
 
-    !(lxmax <= bxmin || lxmin >= bxmax || lymin >= bymax || lymax <= bymin)
+        let mut x = rng.range(1..step);
+        let mut height = 0;
+        let mut lines = vec![format!("{x},0")];
+        for _ in 0..size {
+            let previous = height;
+            while height == previous {
+                height = rng.range(1..100_000);
+            }
+            lines.push(format!("{x},{height}"));
+            x += rng.range(1..step);
+            lines.push(format!("{x},{height}"));
+        }
+        lines.push(format!("{x},0"));
+        lines.join("\n")
+    }
 }
 
 #[cfg(test)]
@@ -79,9 +83,9 @@ mod tests {
 2,5
 2,3
 7,3";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let result = solve_part1(&input);
-        assert_eq!(result, 50);
+        assert_eq!(result, Some(50));
     }
 
     #[test]
@@ -94,8 +98,30 @@ mod tests {
 2,5
 2,3
 7,3";
-        let input = input_generator(input);
+        let input = input_generator(input).unwrap();
         let result = solve_part2(&input);
-        assert_eq!(result, 24);
+        assert_eq!(result, Some(24));
+    }
+
+    #[test]
+    fn test_generated_input() {
+        for seed in 0..5 {
+            let points = input_generator(&generate::input(20, seed)).unwrap();
+            assert_eq!(points.len(), 42);
+            assert!(
+                points
+                    .iter()
+                    .circular_tuple_windows()
+                    .all(|(a, b)| (a.x == b.x) != (a.y == b.y))
+            );
+            assert!(solve_part2(&points) <= solve_part1(&points));
+        }
+    }
+
+    #[test]
+    fn test_single_red_tile() {
+        let input = input_generator("6,0").unwrap();
+        assert_eq!(solve_part1(&input), None);
+        assert_eq!(solve_part2(&input), None);
     }
 }
@@ -1,3 +1,4 @@
+use aoc_core::{parse::PuzzleParseError, player::Simulation};
 use aoc_runner_derive::aoc_lib;
 
 mod day1;
@@ -14,3 +15,29 @@ mod day8;
 mod day9;
 
 aoc_lib! { year = 2025 }
+
+/// Builds the step-based simulation behind a day's part from its input, for days that have one.
+pub fn simulation(
+    day: u8,
+    part: u8,
+    input: &str,
+) -> Option<Result<Box<dyn Simulation>, PuzzleParseError>> {
+    match (day, part) {
+        (4, 2) => Some(
+            day4::input_generator(input)
+                .map(|grid| Box::new(day4::Removal::new(grid)) as Box<dyn Simulation>),
+        ),
+        _ => None,
+    }
+}
+
+/// A random input for a day of `size` (a number of points, columns or devices) from `seed`, for
+/// days that have a generator.
+pub fn generate_input(day: u8, size: usize, seed: u64) -> Option<String> {
+    match day {
+        8 => Some(day8::generate::input(size, seed)),
+        9 => Some(day9::generate::input(size, seed)),
+        11 => Some(day11::generate::input(size, seed)),
+        _ => None,
+    }
+}
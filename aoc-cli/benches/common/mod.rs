@@ -0,0 +1,31 @@
+use std::hint::black_box;
+
+use aoc_cli::solutions::{self, SOLUTIONS};
+use aoc_runner::ArcStr;
+use criterion::Criterion;
+
+/// Benchmarks the generator and solver of every part of `year` against its checked-in input, as
+/// `<year>/day<N>/part<M>/{generator,solver}`. Days without an input file are skipped.
+pub fn bench_year(c: &mut Criterion, year: u32) {
+    for solution in SOLUTIONS.iter().filter(|s| s.year == year) {
+        let Ok(input) = solutions::read_input(&solutions::default_input(year, solution.day)) else {
+            continue;
+        };
+        let input = ArcStr::from(&input);
+
+        let mut name = format!("{year}/day{}/part{}", solution.day, solution.part);
+        if let Some(variant) = solution.name {
+            name = format!("{name}/{variant}");
+        }
+        let mut group = c.benchmark_group(name);
+
+        group.bench_function("generator", |b| {
+            b.iter(|| (solution.build)(black_box(input.clone())).unwrap())
+        });
+
+        let runner = (solution.build)(input).unwrap();
+        group.bench_function("solver", |b| b.iter(|| runner.try_run().unwrap()));
+
+        group.finish();
+    }
+}
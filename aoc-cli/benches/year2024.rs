@@ -0,0 +1,15 @@
+mod common;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+fn benches(c: &mut Criterion) {
+    common::bench_year(c, 2024);
+}
+
+criterion_group! {
+    name = year2024;
+    // Some solvers take seconds per run, so keep the sample count at criterion's minimum.
+    config = Criterion::default().sample_size(10);
+    targets = benches
+}
+criterion_main!(year2024);
@@ -0,0 +1,87 @@
+//! Generates the table of solutions from the `#[aoc(dayN, partM)]` attributes in each year's
+//! sources, so the runner never needs a hand-maintained list.
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+const YEARS: &[u32] = &[2024, 2025];
+
+struct Entry {
+    year: u32,
+    day: u8,
+    part: u8,
+    name: Option<String>,
+}
+
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+    let mut entries = Vec::new();
+    for &year in YEARS {
+        let src = root.join(year.to_string()).join("src");
+        println!("cargo::rerun-if-changed={}", src.display());
+
+        for file in fs::read_dir(&src).unwrap() {
+            let path = file.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                println!("cargo::rerun-if-changed={}", path.display());
+                let source = fs::read_to_string(&path).unwrap();
+                entries.extend(
+                    source
+                        .lines()
+                        .filter_map(|line| parse_attribute(year, line)),
+                );
+            }
+        }
+    }
+    entries.sort_by(|a, b| (a.year, a.day, a.part, &a.name).cmp(&(b.year, b.day, b.part, &b.name)));
+
+    let mut out = String::from("pub static SOLUTIONS: &[Solution] = &[\n");
+    for Entry {
+        year,
+        day,
+        part,
+        name,
+    } in &entries
+    {
+        let (trait_name, fn_name) = match name {
+            Some(name) => (
+                format!("Day{day}Part{part}{}", name.to_uppercase()),
+                format!("day{day}_part{part}_{}", name.to_lowercase()),
+            ),
+            None => (
+                format!("Day{day}Part{part}"),
+                format!("day{day}_part{part}"),
+            ),
+        };
+        writeln!(
+            out,
+            "    Solution {{ year: {year}, day: {day}, part: {part}, name: {name:?}, \
+             build: <advent_of_code_{year}::Factory as advent_of_code_{year}::{trait_name}>::{fn_name} }},",
+        )
+        .unwrap();
+    }
+    out.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+}
+
+/// Parses a line such as `#[aoc(day15, part2)]` or `#[aoc(day1, part1, fast)]`.
+fn parse_attribute(year: u32, line: &str) -> Option<Entry> {
+    let args = line.trim().strip_prefix("#[aoc(")?.strip_suffix(")]")?;
+    let mut args = args.split(',').map(str::trim);
+
+    let day = args.next()?.strip_prefix("day")?.parse().ok()?;
+    let part = args.next()?.strip_prefix("part")?.parse().ok()?;
+    let name = args
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(String::from);
+
+    Some(Entry {
+        year,
+        day,
+        part,
+        name,
+    })
+}
@@ -0,0 +1,169 @@
+//! The known answers for the checked-in inputs, kept in `answers.toml` at the workspace root:
+//!
+//! ```toml
+//! [2024.day15]
+//! part1 = 1318523
+//! part2 = 1337648
+//! ```
+//!
+//! Answers are integers or quoted strings, and are compared against the solver's output as text.
+//!
+//! A directory of inputs for one day can have its own `answers.toml`, with a section named after
+//! each input file without its extension, e.g. `[alice]` for `alice.txt`.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{manifest, workspace_root};
+
+pub fn default_path() -> PathBuf {
+    workspace_root().join("answers.toml")
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u32, u8, u8), String>);
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&contents).map_err(|e| format!("{}:{e}", path.display()))
+    }
+
+    /// Parses the manifest, reporting errors as `line: message`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let answers = parse_sections(input, "[<year>.day<N>]", parse_section)?;
+        Ok(Self(
+            answers
+                .into_iter()
+                .map(|(((year, day), part), value)| ((year, day, part), value))
+                .collect(),
+        ))
+    }
+
+    pub fn get(&self, year: u32, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(year, day, part)).map(String::as_str)
+    }
+}
+
+/// The answers for a directory of inputs to a single day, keyed by input name.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct InputAnswers(BTreeMap<(String, u8), String>);
+
+impl InputAnswers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&contents).map_err(|e| format!("{}:{e}", path.display()))
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        parse_sections(input, "[<input name>]", |header| {
+            Some(header.to_string()).filter(|name| !name.is_empty())
+        })
+        .map(Self)
+    }
+
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        self.0.get(&(input.to_string(), part)).map(String::as_str)
+    }
+}
+
+/// Parses `part<N> = <answer>` lines grouped under `[<section>]` headers.
+fn parse_sections<K: Ord + Clone>(
+    input: &str,
+    header_format: &str,
+    parse_section: impl Fn(&str) -> Option<K>,
+) -> Result<BTreeMap<(K, u8), String>, String> {
+    let mut answers = BTreeMap::new();
+    manifest::parse(input, header_format, parse_section, |key, name, value| {
+        let key = key.ok_or_else(|| format!("answer outside a {header_format} section"))?;
+        let part = name
+            .strip_prefix("part")
+            .and_then(|part| part.parse().ok())
+            .ok_or("expected part1 or part2")?;
+        let value = parse_value(value).ok_or("expected an integer or a quoted string")?;
+        if answers.insert((key.clone(), part), value).is_some() {
+            return Err("duplicate answer".to_string());
+        }
+        Ok(())
+    })?;
+    Ok(answers)
+}
+
+fn parse_section(header: &str) -> Option<(u32, u8)> {
+    let (year, day) = header.split_once('.')?;
+    Some((year.parse().ok()?, day.strip_prefix("day")?.parse().ok()?))
+}
+
+/// The answer as written, so that integers of any size compare as the text solvers print.
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        return quoted.strip_suffix('"').map(String::from);
+    }
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let canonical = digits == "0" || !digits.starts_with('0');
+    (!digits.is_empty() && canonical && digits.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            "# comment
+[2024.day15]
+part1 = 1318523
+part2 = \"ABC\" # trailing comment
+
+[2025.day12]
+part1 = 443
+part2 = 123456789012345678901234567890",
+        )
+        .unwrap();
+        assert_eq!(answers.get(2024, 15, 1), Some("1318523"));
+        assert_eq!(answers.get(2024, 15, 2), Some("ABC"));
+        assert_eq!(answers.get(2025, 12, 1), Some("443"));
+        assert_eq!(
+            answers.get(2025, 12, 2),
+            Some("123456789012345678901234567890")
+        );
+        assert_eq!(answers.get(2025, 11, 1), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Answers::parse("part1 = 1"),
+            Err("1: answer outside a [<year>.day<N>] section".to_string())
+        );
+        assert!(Answers::parse("[2024.day1]\npart1 = 1\npart1 = 2").is_err());
+        assert!(Answers::parse("[2024.day1]\npart1 = one").is_err());
+        assert!(Answers::parse("[2024.day1]\npart1 = 007").is_err());
+        assert!(Answers::parse("[2024.day1]\npart1 = -").is_err());
+        assert!(Answers::parse("[2024]").is_err());
+    }
+
+    #[test]
+    fn test_parse_input_answers() {
+        let answers = InputAnswers::parse("[alice]\npart1 = 11\n[bob]\npart2 = \"x\"").unwrap();
+        assert_eq!(answers.get("alice", 1), Some("11"));
+        assert_eq!(answers.get("bob", 2), Some("x"));
+        assert_eq!(answers.get("bob", 1), None);
+        assert_eq!(
+            InputAnswers::parse("part1 = 1"),
+            Err("1: answer outside a [<input name>] section".to_string())
+        );
+    }
+
+    #[test]
+    fn test_manifest_parses() {
+        Answers::load(&default_path()).unwrap();
+    }
+}
@@ -0,0 +1,782 @@
+use std::{path::PathBuf, time::Duration};
+
+use aoc_cli::fuzz::Config as FuzzConfig;
+use aoc_core::{diag::Level, render::ImageFormat};
+
+pub const USAGE: &str = "\
+usage: aoc run <year> [day] [--part <1|2>] [--input <path|->] [--json] [-v | --trace] [--parallel]
+       aoc run <year> <day> --inputs <dir> [--part <1|2>] [--json] [-v | --trace]
+       aoc play <year> <day> [--part <1|2>] [--input <path>] [--fps <n>] [--paused]
+       aoc export <year> <day> [--part <1|2>] [--input <path>] [--frame <n>] [--frames <n>]
+                  [--scale <n>] [--format <png|ppm|svg>] [--out <dir>]
+       aoc generate <year> <day> --size <n> [--seed <n>]
+       aoc fuzz <year> [day] [--part <1|2>] [--cases <n>] [--seed <n>] [--size <n>]
+                [--timeout <secs>]
+       aoc history [year] [day] [--part <1|2>]
+       aoc new <year> <day>
+       aoc fetch <year> <day>
+       aoc submit <year> <day> --part <1|2> [answer]
+
+commands:
+  run             print the answers, with generator and solver times, recording the times
+                  on the checked-in inputs in perf-history.jsonl (or AOC_HISTORY) unless
+                  --input, --inputs or --parallel is given
+  play            animate a day's simulation in the terminal
+  export          write frames of a day's simulation as images
+  generate        print a random input for a day, for stress testing
+  fuzz            look for inputs that make a day's generator or solvers panic or hang,
+                  printing the smallest such input found
+  history         print how the recorded times on this machine (AOC_MACHINE, or the host
+                  name) have changed, slowest first
+  new             start a day: write <year>/src/day<day>.rs from a template, declare it in
+                  lib.rs, and create an empty input file
+  fetch           download a day's input to <year>/input/<year>/day<day>.txt
+  submit          submit an answer, or the solution's answer if none is given
+
+  fetch and submit read the session cookie from AOC_SESSION, and talk to AOC_BASE_URL
+  instead of https://adventofcode.com if it is set
+
+  <year>          puzzle year, e.g. 2024
+  [day]           run a single day instead of the whole year
+  --part, -p      run only this part
+  --input, -i     read the input from a file, or from stdin with '-'
+                  (defaults to <year>/input/<year>/day<day>.txt)
+  --inputs        run the day on every input in a directory and print a table of the
+                  results, checking them against <dir>/answers.toml if it exists, with a
+                  [<name>] section of part1 and part2 answers for each <name>.txt
+  --json          print a JSON report of the answers and times instead of text
+  --verbose, -v   print solvers' diagnostic values to stderr
+  --trace         also print solvers' diagnostic renders, such as whole maps
+  --parallel      run each day on its own thread, printing once all have finished
+                  (times then include contention between days)
+  --fps           frames per second when playing (default 10)
+  --paused        start playing paused on the first frame
+  --frame         first frame to export (default 0)
+  --frames        number of frames to export (default 1)
+  --scale         pixels per cell, or SVG units per cell (default 4)
+  --format        image format (default png)
+  --out, -o       directory for the frames (default frames)
+  --size          size of the generated input: a grid's side, or a number of lines
+  --seed          seed for the generated input (default 0)
+  --cases         number of random inputs to fuzz each day with (default 256)
+  --timeout       seconds a part may take on one fuzzed input before it counts as hung
+                  (default 2)";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Play(PlayArgs),
+    Export(ExportArgs),
+    Generate(GenerateArgs),
+    Fuzz(FuzzArgs),
+    History(HistoryArgs),
+    New(NewArgs),
+    Fetch(FetchArgs),
+    Submit(SubmitArgs),
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub year: u32,
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub input: Option<Input>,
+    pub inputs: Option<PathBuf>,
+    pub json: bool,
+    pub diagnostics: Level,
+    pub parallel: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PlayArgs {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<PathBuf>,
+    pub fps: f64,
+    pub paused: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExportArgs {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<PathBuf>,
+    pub frame: usize,
+    pub frames: usize,
+    pub scale: usize,
+    pub format: ImageFormat,
+    pub out: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GenerateArgs {
+    pub year: u32,
+    pub day: u8,
+    pub size: usize,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FuzzArgs {
+    pub year: u32,
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub config: FuzzConfig,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct HistoryArgs {
+    pub year: Option<u32>,
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NewArgs {
+    pub year: u32,
+    pub day: u8,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FetchArgs {
+    pub year: u32,
+    pub day: u8,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SubmitArgs {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+/// The options each command takes, besides `<year> [day]` and the like.
+const COMMANDS: &[(&str, &[&str])] = &[
+    (
+        "run",
+        &[
+            "--part",
+            "--input",
+            "--inputs",
+            "--json",
+            "--verbose",
+            "--trace",
+            "--parallel",
+        ],
+    ),
+    ("play", &["--part", "--input", "--fps", "--paused"]),
+    (
+        "export",
+        &[
+            "--part", "--input", "--frame", "--frames", "--scale", "--format", "--out",
+        ],
+    ),
+    ("generate", &["--size", "--seed"]),
+    (
+        "fuzz",
+        &["--part", "--cases", "--seed", "--size", "--timeout"],
+    ),
+    ("history", &["--part"]),
+    ("new", &[]),
+    ("fetch", &[]),
+    ("submit", &["--part"]),
+];
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+
+    let command = match args.next() {
+        Some(command) if !matches!(command.as_str(), "help" | "--help" | "-h") => command,
+        _ => return Ok(Command::Help),
+    };
+    let &(command, allowed) = COMMANDS
+        .iter()
+        .find(|(name, _)| *name == command)
+        .ok_or_else(|| format!("unknown command '{command}'"))?;
+
+    let options = parse_options(args)?;
+    if let Some(flag) = options.given.iter().find(|flag| !allowed.contains(flag)) {
+        return Err(match allowed {
+            [] => format!("'aoc {command}' takes no options"),
+            [only] => format!("'aoc {command}' only takes {only}, not {flag}"),
+            [init @ .., last] => format!(
+                "'aoc {command}' only takes {} and {last}, not {flag}",
+                init.join(", ")
+            ),
+        });
+    }
+
+    match command {
+        "run" => parse_run(options).map(Command::Run),
+        "play" => parse_play(options).map(Command::Play),
+        "export" => parse_export(options).map(Command::Export),
+        "generate" => parse_generate(options).map(Command::Generate),
+        "fuzz" => parse_fuzz(options).map(Command::Fuzz),
+        "history" => parse_history(options).map(Command::History),
+        "new" => parse_new(options).map(Command::New),
+        "fetch" => parse_fetch(options).map(Command::Fetch),
+        "submit" => parse_submit(options).map(Command::Submit),
+        _ => unreachable!("every command in COMMANDS is parsed"),
+    }
+}
+
+/// The arguments shared by every command, before checking which ones the command accepts.
+#[derive(Default)]
+struct Options {
+    /// The long name of every option given, as listed in [`COMMANDS`].
+    given: Vec<&'static str>,
+    positional: Vec<String>,
+    part: Option<u8>,
+    input: Option<Input>,
+    inputs: Option<PathBuf>,
+    json: bool,
+    diagnostics: Option<Level>,
+    parallel: bool,
+    fps: Option<f64>,
+    paused: bool,
+    frame: Option<usize>,
+    frames: Option<usize>,
+    scale: Option<usize>,
+    format: Option<ImageFormat>,
+    out: Option<PathBuf>,
+    size: Option<usize>,
+    seed: Option<u64>,
+    cases: Option<usize>,
+    timeout: Option<Duration>,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        let long = match arg.as_str() {
+            "-p" => "--part",
+            "-i" => "--input",
+            "-v" => "--verbose",
+            "-o" => "--out",
+            flag => flag,
+        };
+        if let Some(&name) = COMMANDS
+            .iter()
+            .flat_map(|(_, allowed)| *allowed)
+            .find(|&&name| name == long)
+        {
+            options.given.push(name);
+        }
+
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                options.part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("invalid part '{value}', expected 1 or 2")),
+                };
+            }
+            "--input" | "-i" => {
+                let value = args.next().ok_or("--input needs a value")?;
+                options.input = Some(match value.as_str() {
+                    "-" => Input::Stdin,
+                    _ => Input::File(value.into()),
+                });
+            }
+            "--inputs" => {
+                options.inputs = Some(args.next().ok_or("--inputs needs a value")?.into());
+            }
+            "--json" => options.json = true,
+            "--verbose" | "-v" => {
+                options.diagnostics = options.diagnostics.max(Some(Level::Debug));
+            }
+            "--trace" => options.diagnostics = Some(Level::Trace),
+            "--parallel" => options.parallel = true,
+            "--fps" => {
+                let value = args.next().ok_or("--fps needs a value")?;
+                options.fps = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|&fps: &f64| fps > 0.0)
+                        .ok_or(format!("invalid fps '{value}'"))?,
+                );
+            }
+            "--paused" => options.paused = true,
+            "--frame" => options.frame = Some(parse_count(&mut args, "--frame", 0)?),
+            "--frames" => options.frames = Some(parse_count(&mut args, "--frames", 1)?),
+            "--scale" => options.scale = Some(parse_count(&mut args, "--scale", 1)?),
+            "--format" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                options.format = Some(ImageFormat::from_extension(&value).ok_or(format!(
+                    "invalid format '{value}', expected png, ppm or svg"
+                ))?);
+            }
+            "--out" | "-o" => {
+                options.out = Some(args.next().ok_or("--out needs a value")?.into());
+            }
+            "--size" => options.size = Some(parse_count(&mut args, "--size", 1)?),
+            "--seed" => {
+                let value = args.next().ok_or("--seed needs a value")?;
+                options.seed = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid seed '{value}'"))?,
+                );
+            }
+            "--cases" => options.cases = Some(parse_count(&mut args, "--cases", 0)?),
+            "--timeout" => {
+                let value = args.next().ok_or("--timeout needs a value")?;
+                options.timeout = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|&secs: &f64| secs > 0.0 && secs.is_finite())
+                        .map(Duration::from_secs_f64)
+                        .ok_or(format!("invalid timeout '{value}'"))?,
+                );
+            }
+            // Everything after `--` is positional, even if it looks like a flag.
+            "--" => options.positional.extend(args.by_ref()),
+            // A negative number, such as an answer, is a value rather than a flag.
+            flag if flag.starts_with('-') && flag.parse::<i128>().is_err() => {
+                return Err(format!("unknown option '{flag}'"));
+            }
+            _ => options.positional.push(arg),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Parses the value of `flag` as a whole number no smaller than `min`.
+fn parse_count(
+    args: &mut impl Iterator<Item = String>,
+    flag: &str,
+    min: usize,
+) -> Result<usize, String> {
+    let value = args.next().ok_or(format!("{flag} needs a value"))?;
+    value
+        .parse()
+        .ok()
+        .filter(|&n| n >= min)
+        .ok_or(format!("invalid {flag} '{value}'"))
+}
+
+/// Parses the `<year> [day]` positional arguments.
+fn parse_year_day(positional: Vec<String>) -> Result<(u32, Option<u8>), String> {
+    let mut positional = positional.into_iter();
+    let year = positional.next().ok_or("missing year")?;
+    let year = year.parse().map_err(|_| format!("invalid year '{year}'"))?;
+    let day = positional
+        .next()
+        .map(|day| {
+            day.parse()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .ok_or(format!("invalid day '{day}'"))
+        })
+        .transpose()?;
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument '{extra}'"));
+    }
+    Ok((year, day))
+}
+
+fn parse_run(options: Options) -> Result<RunArgs, String> {
+    let (year, day) = parse_year_day(options.positional)?;
+    if options.input.is_some() && day.is_none() {
+        return Err("--input needs a day, as each day has its own input".to_string());
+    }
+    if options.inputs.is_some() {
+        if day.is_none() {
+            return Err("--inputs needs a day, as each day has its own inputs".to_string());
+        }
+        if options.input.is_some() || options.parallel {
+            return Err("--inputs can't be used with --input or --parallel".to_string());
+        }
+    }
+
+    Ok(RunArgs {
+        year,
+        day,
+        part: options.part,
+        input: options.input,
+        inputs: options.inputs,
+        json: options.json,
+        diagnostics: options.diagnostics.unwrap_or(Level::Off),
+        parallel: options.parallel,
+    })
+}
+
+fn parse_play(options: Options) -> Result<PlayArgs, String> {
+    let (year, day) = parse_year_day(options.positional)?;
+    let input = match options.input {
+        Some(Input::Stdin) => {
+            return Err("'aoc play' reads its controls from stdin, so needs an input file".into());
+        }
+        Some(Input::File(path)) => Some(path),
+        None => None,
+    };
+
+    Ok(PlayArgs {
+        year,
+        day: day.ok_or("missing day")?,
+        part: options.part.unwrap_or(1),
+        input,
+        fps: options.fps.unwrap_or(10.0),
+        paused: options.paused,
+    })
+}
+
+fn parse_export(options: Options) -> Result<ExportArgs, String> {
+    let (year, day) = parse_year_day(options.positional)?;
+    let input = match options.input {
+        Some(Input::Stdin) => return Err("'aoc export' needs an input file".into()),
+        Some(Input::File(path)) => Some(path),
+        None => None,
+    };
+
+    Ok(ExportArgs {
+        year,
+        day: day.ok_or("missing day")?,
+        part: options.part.unwrap_or(1),
+        input,
+        frame: options.frame.unwrap_or(0),
+        frames: options.frames.unwrap_or(1),
+        scale: options.scale.unwrap_or(4),
+        format: options.format.unwrap_or(ImageFormat::Png),
+        out: options.out.unwrap_or_else(|| "frames".into()),
+    })
+}
+
+fn parse_generate(options: Options) -> Result<GenerateArgs, String> {
+    let (year, day) = parse_year_day(options.positional)?;
+    Ok(GenerateArgs {
+        year,
+        day: day.ok_or("missing day")?,
+        size: options.size.ok_or("missing --size")?,
+        seed: options.seed.unwrap_or(0),
+    })
+}
+
+fn parse_fuzz(options: Options) -> Result<FuzzArgs, String> {
+    let (year, day) = parse_year_day(options.positional)?;
+    let defaults = FuzzConfig::default();
+
+    Ok(FuzzArgs {
+        year,
+        day,
+        part: options.part,
+        config: FuzzConfig {
+            cases: options.cases.unwrap_or(defaults.cases),
+            seed: options.seed.unwrap_or(defaults.seed),
+            size: options.size.unwrap_or(defaults.size),
+            timeout: options.timeout.unwrap_or(defaults.timeout),
+        },
+    })
+}
+
+fn parse_history(options: Options) -> Result<HistoryArgs, String> {
+    let (year, day) = if options.positional.is_empty() {
+        (None, None)
+    } else {
+        let (year, day) = parse_year_day(options.positional)?;
+        (Some(year), day)
+    };
+    Ok(HistoryArgs {
+        year,
+        day,
+        part: options.part,
+    })
+}
+
+fn parse_new(options: Options) -> Result<NewArgs, String> {
+    let (year, day) = parse_year_day(options.positional)?;
+    Ok(NewArgs {
+        year,
+        day: day.ok_or("missing day")?,
+    })
+}
+
+fn parse_fetch(options: Options) -> Result<FetchArgs, String> {
+    let (year, day) = parse_year_day(options.positional)?;
+    Ok(FetchArgs {
+        year,
+        day: day.ok_or("missing day")?,
+    })
+}
+
+fn parse_submit(mut options: Options) -> Result<SubmitArgs, String> {
+    let answer = (options.positional.len() > 2).then(|| options.positional.remove(2));
+    let (year, day) = parse_year_day(options.positional)?;
+    Ok(SubmitArgs {
+        year,
+        day: day.ok_or("missing day")?,
+        part: options.part.ok_or("missing --part")?,
+        answer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Command, String> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse_str("run 2024 15 --part 2 --input path/to/file.txt"),
+            Ok(Command::Run(RunArgs {
+                year: 2024,
+                day: Some(15),
+                part: Some(2),
+                input: Some(Input::File("path/to/file.txt".into())),
+                inputs: None,
+                json: false,
+                diagnostics: Level::Off,
+                parallel: false,
+            }))
+        );
+        assert_eq!(
+            parse_str("run 2025 --json --trace --parallel"),
+            Ok(Command::Run(RunArgs {
+                year: 2025,
+                day: None,
+                part: None,
+                input: None,
+                inputs: None,
+                json: true,
+                diagnostics: Level::Trace,
+                parallel: true,
+            }))
+        );
+        assert_eq!(
+            parse_str("run -i - 2025 3"),
+            Ok(Command::Run(RunArgs {
+                year: 2025,
+                day: Some(3),
+                part: None,
+                input: Some(Input::Stdin),
+                inputs: None,
+                json: false,
+                diagnostics: Level::Off,
+                parallel: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_run_inputs() {
+        assert_eq!(
+            parse_str("run 2024 6 --inputs shared/2024/day6 -p 2"),
+            Ok(Command::Run(RunArgs {
+                year: 2024,
+                day: Some(6),
+                part: Some(2),
+                input: None,
+                inputs: Some("shared/2024/day6".into()),
+                json: false,
+                diagnostics: Level::Off,
+                parallel: false,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_play() {
+        assert_eq!(
+            parse_str("play 2024 15 -p 2 --fps 30 --paused"),
+            Ok(Command::Play(PlayArgs {
+                year: 2024,
+                day: 15,
+                part: 2,
+                input: None,
+                fps: 30.0,
+                paused: true,
+            }))
+        );
+        assert!(parse_str("play 2024").is_err());
+        assert!(parse_str("play 2024 6 -i -").is_err());
+        assert!(parse_str("play 2024 6 --fps 0").is_err());
+        assert!(parse_str("run 2024 6 --paused").is_err());
+        assert!(parse_str("play 2024 6 --json").is_err());
+        assert!(parse_str("play 2024 6 --parallel").is_err());
+        assert!(parse_str("export 2024 6 -v").is_err());
+    }
+
+    #[test]
+    fn test_parse_export() {
+        assert_eq!(
+            parse_str("export 2024 14 --frame 7753 --scale 2 --format svg -o tree"),
+            Ok(Command::Export(ExportArgs {
+                year: 2024,
+                day: 14,
+                part: 1,
+                input: None,
+                frame: 7753,
+                frames: 1,
+                scale: 2,
+                format: ImageFormat::Svg,
+                out: "tree".into(),
+            }))
+        );
+        assert!(parse_str("export 2024 15 --format gif").is_err());
+        assert!(parse_str("export 2024 15 --frames 0").is_err());
+        assert!(parse_str("export 2024 15 --fps 5").is_err());
+        assert!(parse_str("play 2024 15 --scale 2").is_err());
+        assert!(parse_str("run 2024 15 --out frames").is_err());
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        assert_eq!(
+            parse_str("generate 2025 11 --size 1000 --seed 7"),
+            Ok(Command::Generate(GenerateArgs {
+                year: 2025,
+                day: 11,
+                size: 1000,
+                seed: 7,
+            }))
+        );
+        assert!(parse_str("generate 2024 6").is_err());
+        assert!(parse_str("generate 2024 6 --size 0").is_err());
+        assert!(parse_str("generate 2024 6 --size 10 --seed -1").is_err());
+        assert!(parse_str("generate 2024 6 --size 10 -p 1").is_err());
+        assert!(parse_str("generate 2024 6 --size 10 --json").is_err());
+        assert!(parse_str("run 2024 6 --seed 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        assert_eq!(
+            parse_str("fuzz 2025 6 -p 2 --cases 1000 --timeout 0.5"),
+            Ok(Command::Fuzz(FuzzArgs {
+                year: 2025,
+                day: Some(6),
+                part: Some(2),
+                config: FuzzConfig {
+                    cases: 1000,
+                    timeout: Duration::from_millis(500),
+                    ..FuzzConfig::default()
+                },
+            }))
+        );
+        assert_eq!(
+            parse_str("fuzz 2024 --seed 3"),
+            Ok(Command::Fuzz(FuzzArgs {
+                year: 2024,
+                day: None,
+                part: None,
+                config: FuzzConfig {
+                    seed: 3,
+                    ..FuzzConfig::default()
+                },
+            }))
+        );
+        assert!(parse_str("fuzz 2024 2 --timeout 0").is_err());
+        assert!(parse_str("fuzz 2024 2 -i day2.txt").is_err());
+        assert!(parse_str("fuzz 2024 2 --json").is_err());
+        assert!(parse_str("run 2024 2 --cases 10").is_err());
+        assert!(parse_str("generate 2024 2 --size 5 --timeout 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_history() {
+        assert_eq!(
+            parse_str("history"),
+            Ok(Command::History(HistoryArgs {
+                year: None,
+                day: None,
+                part: None,
+            }))
+        );
+        assert_eq!(
+            parse_str("history 2024 6 -p 2"),
+            Ok(Command::History(HistoryArgs {
+                year: Some(2024),
+                day: Some(6),
+                part: Some(2),
+            }))
+        );
+        assert!(parse_str("history 2024 --json").is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(
+            parse_str("new 2025 13"),
+            Ok(Command::New(NewArgs {
+                year: 2025,
+                day: 13,
+            }))
+        );
+        assert!(parse_str("new 2025").is_err());
+        assert!(parse_str("new 2025 26").is_err());
+        assert!(parse_str("new 2025 13 -p 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_fetch_and_submit() {
+        assert_eq!(
+            parse_str("fetch 2024 3"),
+            Ok(Command::Fetch(FetchArgs { year: 2024, day: 3 }))
+        );
+        assert_eq!(
+            parse_str("submit 2024 3 -p 2 48"),
+            Ok(Command::Submit(SubmitArgs {
+                year: 2024,
+                day: 3,
+                part: 2,
+                answer: Some("48".to_string()),
+            }))
+        );
+        assert_eq!(
+            parse_str("submit 2024 3 --part 1"),
+            Ok(Command::Submit(SubmitArgs {
+                year: 2024,
+                day: 3,
+                part: 1,
+                answer: None,
+            }))
+        );
+        for args in ["submit 2024 1 -5 --part 1", "submit 2024 1 -p 1 -- -5"] {
+            assert_eq!(
+                parse_str(args),
+                Ok(Command::Submit(SubmitArgs {
+                    year: 2024,
+                    day: 1,
+                    part: 1,
+                    answer: Some("-5".to_string()),
+                }))
+            );
+        }
+        assert!(parse_str("fetch 2024").is_err());
+        assert!(parse_str("fetch 2024 3 -p 1").is_err());
+        assert!(parse_str("submit 2024 3 -p 1 -5x").is_err());
+        assert!(parse_str("submit 2024 3 48").is_err());
+        assert!(parse_str("submit 2024 3 -p 1 48 49").is_err());
+        assert!(parse_str("submit 2024 3 -p 1 --json").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_str("run").is_err());
+        assert!(parse_str("run 2024 26").is_err());
+        assert!(parse_str("run 2024 1 --part 3").is_err());
+        assert!(parse_str("run 2024 --input -").is_err());
+        assert!(parse_str("run 2024 --inputs shared").is_err());
+        assert!(parse_str("run 2024 6 --inputs shared -i day6.txt").is_err());
+        assert!(parse_str("frobnicate").is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! Time budgets for the checked-in inputs, kept in `budgets.toml` at the workspace root:
+//!
+//! ```toml
+//! default = 2000
+//!
+//! [2024]
+//! day6 = 20000
+//! ```
+//!
+//! Budgets are in milliseconds, for a part's generator and solver together. A day without a
+//! budget of its own gets the default, if there is one.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{manifest, workspace_root};
+
+pub fn default_path() -> PathBuf {
+    workspace_root().join("budgets.toml")
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Budgets {
+    default: Option<Duration>,
+    days: BTreeMap<(u32, u8), Duration>,
+}
+
+impl Budgets {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&contents).map_err(|e| format!("{}:{e}", path.display()))
+    }
+
+    /// Parses the budgets, reporting errors as `line: message`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut budgets = Self::default();
+        manifest::parse(
+            input,
+            "[<year>]",
+            |header| header.parse().ok(),
+            |year: Option<&u32>, name, value| {
+                let budget = value
+                    .parse()
+                    .map(Duration::from_millis)
+                    .map_err(|_| "expected a whole number of milliseconds")?;
+                let duplicate = match (year, name) {
+                    (None, "default") => budgets.default.replace(budget).is_some(),
+                    (None, _) => {
+                        return Err("only the default goes before a [<year>] header".into());
+                    }
+                    (Some(&year), name) => {
+                        let day = name
+                            .strip_prefix("day")
+                            .and_then(|day| day.parse().ok())
+                            .ok_or("expected day<N>")?;
+                        budgets.days.insert((year, day), budget).is_some()
+                    }
+                };
+                if duplicate {
+                    return Err("duplicate budget".to_string());
+                }
+                Ok(())
+            },
+        )?;
+        Ok(budgets)
+    }
+
+    /// The budget for each part of a day.
+    pub fn get(&self, year: u32, day: u8) -> Option<Duration> {
+        self.days.get(&(year, day)).copied().or(self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let budgets = Budgets::parse(
+            "# comment
+default = 500
+
+[2024]
+day6 = 20000 # trailing comment",
+        )
+        .unwrap();
+        assert_eq!(budgets.get(2024, 6), Some(Duration::from_secs(20)));
+        assert_eq!(budgets.get(2024, 7), Some(Duration::from_millis(500)));
+        assert_eq!(
+            Budgets::parse("[2025]\nday1 = 10").unwrap().get(2025, 2),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Budgets::parse("day1 = 10"),
+            Err("1: only the default goes before a [<year>] header".to_string())
+        );
+        assert!(Budgets::parse("[2024]\nday1 = 1\nday1 = 2").is_err());
+        assert!(Budgets::parse("[2024]\nday1 = 1.5").is_err());
+        assert!(Budgets::parse("[2024]\npart1 = 1").is_err());
+        assert!(Budgets::parse("[2024.day1]").is_err());
+    }
+
+    #[test]
+    fn test_manifest_parses() {
+        Budgets::load(&default_path()).unwrap();
+    }
+}
@@ -0,0 +1,378 @@
+//! Fetches puzzle inputs from Advent of Code and submits answers, the way the website expects
+//! tools to: inputs are cached on disk so each is only downloaded once, and requests are spaced
+//! out so that a loop can't hammer the server. The time of the last request is kept in
+//! `<cache_dir>/.aoc/last-request`, so the spacing holds across separate runs of `aoc` too.
+//!
+//! The [`mock`](crate::mock) server implements the same endpoints for testing offline.
+
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ureq::Agent;
+
+use crate::workspace_root;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The shortest time between two requests to the server.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = concat!("aoc-cli/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The session token was rejected, or there wasn't one.
+    Unauthorized,
+    /// The puzzle hasn't unlocked yet, or doesn't exist.
+    NotFound {
+        year: u32,
+        day: u8,
+    },
+    /// Any other response that isn't a success.
+    Status {
+        status: u16,
+        body: String,
+    },
+    Transport(ureq::Error),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unauthorized => write!(f, "the session token was rejected"),
+            Self::NotFound { year, day } => write!(f, "{year} day {day} isn't available yet"),
+            Self::Status { status, body } => {
+                write!(f, "unexpected response {status}: {}", body.trim())
+            }
+            Self::Transport(error) => write!(f, "request failed: {error}"),
+            Self::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Transport(error) => Some(error),
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(error: ureq::Error) -> Self {
+        Self::Transport(error)
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Whether an answer hit the mark, from the text of the page the server returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect {
+        hint: Option<Hint>,
+    },
+    /// Submitted too soon after a wrong answer, with the wait if the page said how long.
+    TooSoon {
+        wait: Option<Duration>,
+    },
+    /// The part was solved already, or part 1 hasn't been solved yet.
+    WrongLevel,
+    /// A page that didn't match any known response, stripped down to its text.
+    Unknown(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "that's the right answer"),
+            Self::Incorrect { hint: None } => write!(f, "that's not the right answer"),
+            Self::Incorrect {
+                hint: Some(Hint::TooHigh),
+            } => write!(f, "that's not the right answer, it's too high"),
+            Self::Incorrect {
+                hint: Some(Hint::TooLow),
+            } => write!(f, "that's not the right answer, it's too low"),
+            Self::TooSoon { wait: Some(wait) } => {
+                write!(f, "answered too recently, wait {}s", wait.as_secs())
+            }
+            Self::TooSoon { wait: None } => write!(f, "answered too recently"),
+            Self::WrongLevel => write!(f, "that part is already solved, or not unlocked yet"),
+            Self::Unknown(text) => write!(f, "unrecognised response: {text}"),
+        }
+    }
+}
+
+/// Works out the verdict from the HTML page returned for a submitted answer.
+pub fn parse_verdict(html: &str) -> Verdict {
+    let text = page_text(html);
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Verdict::Incorrect { hint }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::TooSoon {
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+/// The text of the page's `<article>`, or of the whole page if it has none, without any tags.
+fn page_text(html: &str) -> String {
+    let article = html
+        .find("<article")
+        .and_then(|start| {
+            let end = html[start..].find("</article>")?;
+            Some(&html[start..start + end])
+        })
+        .unwrap_or(html);
+
+    let mut text = String::with_capacity(article.len());
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads e.g. "You have 1m 34s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for part in text[start..end].split_whitespace() {
+        let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let number: u64 = number.parse().ok()?;
+        seconds += match unit {
+            "h" => number * 3600,
+            "m" => number * 60,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+pub struct Client {
+    agent: Agent,
+    session: String,
+    base_url: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    /// A client for adventofcode.com that caches inputs where `aoc run` looks for them.
+    pub fn new(session: impl Into<String>) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+        Self {
+            agent,
+            session: session.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            cache_dir: workspace_root().to_path_buf(),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Inputs are cached as `<cache_dir>/<year>/input/<year>/day<day>.txt`, and the time of the
+    /// last request is kept alongside them.
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn cached_input_path(&self, year: u32, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(format!("{year}/input/{year}/day{day}.txt"))
+    }
+
+    /// The input for a day, from the cache if it has been fetched before. An empty file, such as
+    /// the placeholder `aoc new` leaves, hasn't been fetched yet.
+    pub fn input(&mut self, year: u32, day: u8) -> Result<String, ClientError> {
+        let path = self.cached_input_path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(input) if !input.trim().is_empty() => Ok(input),
+            Ok(_) => self.fetch_input(year, day, &path),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                self.fetch_input(year, day, &path)
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn fetch_input(&mut self, year: u32, day: u8, path: &Path) -> Result<String, ClientError> {
+        self.wait_for_turn()?;
+        let mut response = self
+            .agent
+            .get(format!("{}/{year}/day/{day}/input", self.base_url))
+            .header("Cookie", self.cookie())
+            .call()?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+
+        match status {
+            200 => {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(path, &body)?;
+                Ok(body)
+            }
+            // The site answers a missing or expired session with a 400 and a login prompt.
+            400 | 401 => Err(ClientError::Unauthorized),
+            404 => Err(ClientError::NotFound { year, day }),
+            status => Err(ClientError::Status { status, body }),
+        }
+    }
+
+    pub fn submit(
+        &mut self,
+        year: u32,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, ClientError> {
+        self.wait_for_turn()?;
+        let mut response = self
+            .agent
+            .post(format!("{}/{year}/day/{day}/answer", self.base_url))
+            .header("Cookie", self.cookie())
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+
+        match status {
+            200 => Ok(parse_verdict(&body)),
+            400 | 401 => Err(ClientError::Unauthorized),
+            404 => Err(ClientError::NotFound { year, day }),
+            status => Err(ClientError::Status { status, body }),
+        }
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn last_request_path(&self) -> PathBuf {
+        self.cache_dir.join(".aoc/last-request")
+    }
+
+    /// Sleeps until `min_interval` has passed since the last request by any client sharing the
+    /// cache, then records this one. The time is kept as milliseconds since the Unix epoch.
+    fn wait_for_turn(&self) -> io::Result<()> {
+        let path = self.last_request_path();
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(last) = last {
+            // A last request in the future, e.g. after the clock moved back, counts as just now.
+            let elapsed = SystemTime::now().duration_since(last).unwrap_or_default();
+            thread::sleep(self.min_interval.saturating_sub(elapsed));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, now.as_millis().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            parse_verdict(
+                "<main><article><p>That's the right answer!  You are one gold star closer.</p></article></main>"
+            ),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(
+                "<article><p>That's not the right answer; your answer is too high.  If you're stuck...</p></article>"
+            ),
+            Verdict::Incorrect {
+                hint: Some(Hint::TooHigh)
+            }
+        );
+        assert_eq!(
+            parse_verdict(
+                "<article><p>That's not the right answer.  If you're stuck...</p></article>"
+            ),
+            Verdict::Incorrect { hint: None }
+        );
+        assert_eq!(
+            parse_verdict(
+                "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 34s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>"
+            ),
+            Verdict::TooSoon {
+                wait: Some(Duration::from_secs(94))
+            }
+        );
+        assert_eq!(
+            parse_verdict(
+                "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"
+            ),
+            Verdict::WrongLevel
+        );
+        assert_eq!(
+            parse_verdict("<p>Something <em>else</em></p>"),
+            Verdict::Unknown("Something else".to_string())
+        );
+    }
+}
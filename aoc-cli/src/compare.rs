@@ -0,0 +1,230 @@
+//! Runs a day against a directory of inputs, such as one per account, checking the answers
+//! against the directory's own `answers.toml` where it has them.
+
+use std::{
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_runner::ArcStr;
+use serde::Serialize;
+
+use crate::{
+    answers::InputAnswers,
+    report::{self, Report, Status},
+    solutions::{self, Solution},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// Solved, with no expected answer to compare against.
+    Solved,
+    Match,
+    Mismatch,
+    Failed,
+    Panicked,
+}
+
+/// One part run against one input.
+#[derive(Debug, Serialize)]
+pub struct InputRun {
+    /// The input's file name without its extension.
+    pub input: String,
+    pub expected: Option<String>,
+    pub outcome: Outcome,
+    #[serde(flatten)]
+    pub report: Report,
+}
+
+impl InputRun {
+    fn new(input: String, expected: Option<String>, report: Report) -> Self {
+        let outcome = match (report.status, &report.answer, &expected) {
+            (Status::Ok, Some(answer), Some(expected)) if answer.to_string() == *expected => {
+                Outcome::Match
+            }
+            (Status::Ok, _, Some(_)) => Outcome::Mismatch,
+            (Status::Ok, _, None) => Outcome::Solved,
+            (Status::Panicked, ..) => Outcome::Panicked,
+            _ => Outcome::Failed,
+        };
+        Self {
+            input,
+            expected,
+            outcome,
+            report,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        matches!(self.outcome, Outcome::Solved | Outcome::Match)
+    }
+}
+
+/// The inputs in `dir`, sorted by name. Hidden files and `answers.toml` are skipped.
+pub fn input_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if entry.file_type()?.is_file() && !name.starts_with('.') && name != "answers.toml" {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Runs each of `solutions` on every input in `dir`, input by input.
+pub fn run(solutions: &[&Solution], dir: &Path) -> Result<Vec<InputRun>, String> {
+    let answers_path = dir.join("answers.toml");
+    let answers = if answers_path.exists() {
+        InputAnswers::load(&answers_path)?
+    } else {
+        InputAnswers::default()
+    };
+
+    let files = input_files(dir).map_err(|e| format!("failed to read {}: {e}", dir.display()))?;
+    if files.is_empty() {
+        return Err(format!("no inputs in {}", dir.display()));
+    }
+
+    let mut runs = Vec::new();
+    for path in files {
+        let name = path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        let input = solutions::read_input(&path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let input = ArcStr::from(&input);
+
+        for solution in solutions {
+            let expected = answers.get(&name, solution.part).map(String::from);
+            runs.push(InputRun::new(
+                name.clone(),
+                expected,
+                report::run(solution, &input),
+            ));
+        }
+    }
+    Ok(runs)
+}
+
+/// Lays the runs out as a table, followed by the error of every run that failed.
+pub fn table(runs: &[InputRun]) -> String {
+    let header = [
+        "input",
+        "part",
+        "answer",
+        "expected",
+        "generator",
+        "solver",
+        "result",
+    ];
+    let rows: Vec<[String; 7]> = runs
+        .iter()
+        .map(|run| {
+            let report = &run.report;
+            let part = match report.name {
+                Some(name) => format!("{} ({name})", report.part),
+                None => report.part.to_string(),
+            };
+            let time =
+                |time: Option<_>| time.map_or_else(|| "-".to_string(), |t| format!("{t:.2?}"));
+            [
+                run.input.clone(),
+                part,
+                report
+                    .answer
+                    .as_ref()
+                    .map_or_else(|| "-".to_string(), ToString::to_string),
+                run.expected.clone().unwrap_or_else(|| "-".to_string()),
+                time(report.generator_time),
+                time(report.solver_time),
+                match run.outcome {
+                    Outcome::Solved => "ok",
+                    Outcome::Match => "match",
+                    Outcome::Mismatch => "MISMATCH",
+                    Outcome::Failed => "FAILED",
+                    Outcome::Panicked => "PANICKED",
+                }
+                .to_string(),
+            ]
+        })
+        .collect();
+
+    let mut table = report::table(header, &rows);
+
+    for run in runs {
+        if let Some(error) = &run.report.error {
+            write!(
+                table,
+                "\n{} part {}: {}",
+                run.input,
+                run.report.part,
+                error.trim_end()
+            )
+            .unwrap();
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::SOLUTIONS;
+
+    #[test]
+    fn test_run_directory() {
+        let dir = std::env::temp_dir().join(format!("aoc-compare-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("alice.txt"),
+            "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n",
+        )
+        .unwrap();
+        fs::write(dir.join("bob.txt"), "not a list\n").unwrap();
+        fs::write(
+            dir.join("answers.toml"),
+            "[alice]\npart1 = 11\npart2 = 30\n",
+        )
+        .unwrap();
+
+        let solutions: Vec<_> = SOLUTIONS
+            .iter()
+            .filter(|s| (s.year, s.day, s.name) == (2024, 1, None))
+            .collect();
+        let runs = run(&solutions, &dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let outcomes: Vec<_> = runs
+            .iter()
+            .map(|run| (run.input.as_str(), run.report.part, run.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                ("alice", 1, Outcome::Match),
+                ("alice", 2, Outcome::Mismatch),
+                ("bob", 1, Outcome::Failed),
+                ("bob", 2, Outcome::Failed),
+            ]
+        );
+
+        let table = table(&runs);
+        let mut lines = table.lines();
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("input  part  answer  expected")
+        );
+        assert!(lines.next().unwrap().starts_with("alice  1     11      11"));
+        assert!(table.contains("MISMATCH"));
+        assert!(table.contains("\nbob part 1: "));
+    }
+}
@@ -0,0 +1,356 @@
+//! Feeds arbitrary and mutated inputs to a day's generators and solvers, looking for inputs that
+//! make them panic or hang, and shrinks the first one found to something small enough to read.
+//!
+//! Cases are drawn from proptest strategies: strings over the alphabet of the day's example
+//! inputs, and the examples themselves with a few characters or lines deleted, inserted or
+//! duplicated. The examples are a crop of the checked-in input and a small synthetic input, where
+//! the day has them. A stack overflow aborts the whole process, so can't be reported.
+
+use std::{panic, sync::mpsc, thread, time::Duration};
+
+use aoc_runner::ArcStr;
+use proptest::{
+    char,
+    collection::vec,
+    prelude::*,
+    sample::select,
+    test_runner::{Config as RunnerConfig, RngAlgorithm, TestRng, TestRunner},
+};
+
+use crate::{
+    report::{self, Status},
+    solutions::{self, Solution},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// How many inputs to try, besides the examples.
+    pub cases: usize,
+    pub seed: u64,
+    /// The size of the synthetic example, as for `aoc generate`.
+    pub size: usize,
+    /// How long one part may take on one input before it counts as hung.
+    pub timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cases: 256,
+            seed: 0,
+            size: 8,
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    Panicked(String),
+    TimedOut,
+}
+
+/// An input that one of the solutions panicked or hung on.
+#[derive(Clone)]
+pub struct Failure {
+    pub solution: &'static Solution,
+    pub fault: Fault,
+    /// The smallest input found that still panics, or the original input of a hang.
+    pub input: String,
+    /// How many inputs were tried before this one failed.
+    pub cases: usize,
+}
+
+/// The examples to mutate for a day: the top left of each section of the checked-in input, and
+/// a synthetic input if the day has a generator.
+pub fn examples(year: u32, day: u8, config: &Config) -> Vec<String> {
+    let mut examples = Vec::new();
+    if let Ok(input) = solutions::read_input(&solutions::default_input(year, day)) {
+        examples.push(crop(&input, 8, 16));
+    }
+    if let Some(input) = solutions::generate_input(year, day, config.size, config.seed) {
+        examples.push(input);
+    }
+    examples
+}
+
+/// The first `lines` lines of each blank-line separated section, cut to `width` characters.
+fn crop(input: &str, lines: usize, width: usize) -> String {
+    input
+        .split("\n\n")
+        .map(|section| {
+            section
+                .lines()
+                .take(lines)
+                .map(|line| line.chars().take(width).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// A change to an example, at a position taken modulo the example's length.
+#[derive(Debug, Clone)]
+enum Mutation {
+    Delete(usize, usize),
+    Insert(usize, char),
+    Replace(usize, char),
+    DuplicateLine(usize),
+    DeleteLine(usize),
+    Truncate(usize),
+}
+
+/// Mostly the examples' own characters, so that mutations stay close to something parseable.
+fn character(alphabet: Vec<char>) -> impl Strategy<Value = char> {
+    prop_oneof![9 => select(alphabet), 1 => char::any()]
+}
+
+fn mutation(alphabet: Vec<char>) -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        (any::<usize>(), 1..8usize).prop_map(|(at, len)| Mutation::Delete(at, len)),
+        (any::<usize>(), character(alphabet.clone())).prop_map(|(at, c)| Mutation::Insert(at, c)),
+        (any::<usize>(), character(alphabet)).prop_map(|(at, c)| Mutation::Replace(at, c)),
+        any::<usize>().prop_map(Mutation::DuplicateLine),
+        any::<usize>().prop_map(Mutation::DeleteLine),
+        any::<usize>().prop_map(Mutation::Truncate),
+    ]
+}
+
+fn mutate(input: &str, mutations: &[Mutation]) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for mutation in mutations {
+        let len = chars.len();
+        match *mutation {
+            Mutation::Insert(at, c) => chars.insert(at % (len + 1), c),
+            _ if len == 0 => {}
+            Mutation::Delete(at, n) => {
+                let at = at % len;
+                chars.drain(at..(at + n).min(len));
+            }
+            Mutation::Replace(at, c) => chars[at % len] = c,
+            Mutation::Truncate(at) => chars.truncate(at % len),
+            Mutation::DuplicateLine(at) | Mutation::DeleteLine(at) => {
+                let text: String = chars.iter().collect();
+                let mut lines: Vec<&str> = text.split('\n').collect();
+                let at = at % lines.len();
+                if let Mutation::DuplicateLine(_) = mutation {
+                    lines.insert(at, lines[at]);
+                } else {
+                    lines.remove(at);
+                }
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Arbitrary strings over the examples' alphabet, and mutations of the examples.
+fn inputs(examples: &[String]) -> BoxedStrategy<String> {
+    let mut alphabet: Vec<char> = examples
+        .iter()
+        .flat_map(|example| example.chars())
+        .collect();
+    if alphabet.is_empty() {
+        alphabet.extend(' '..='~');
+    }
+    alphabet.push('\n');
+    alphabet.sort_unstable();
+    alphabet.dedup();
+
+    let arbitrary = vec(character(alphabet.clone()), 0..256).prop_map(String::from_iter);
+    if examples.is_empty() {
+        return arbitrary.boxed();
+    }
+    let mutated = (select(examples.to_vec()), vec(mutation(alphabet), 1..8))
+        .prop_map(|(example, mutations)| mutate(&example, &mutations));
+    prop_oneof![1 => arbitrary, 4 => mutated].boxed()
+}
+
+/// Runs every solution on `input`, returning the first that panics or takes longer than
+/// `timeout`. A hung solution is left running on its own thread.
+fn check(
+    solutions: &[&'static Solution],
+    input: &str,
+    timeout: Duration,
+) -> Option<(&'static Solution, Fault)> {
+    let (sender, receiver) = mpsc::channel();
+    let input = ArcStr::from(input);
+    let owned = solutions.to_vec();
+    thread::spawn(move || {
+        for solution in owned {
+            if sender.send(report::run(solution, &input)).is_err() {
+                break;
+            }
+        }
+    });
+
+    for &solution in solutions {
+        match receiver.recv_timeout(timeout) {
+            Ok(report) if report.status == Status::Panicked => {
+                return Some((solution, Fault::Panicked(report.error.unwrap_or_default())));
+            }
+            Ok(_) => {}
+            Err(_) => return Some((solution, Fault::TimedOut)),
+        }
+    }
+    None
+}
+
+/// Fuzzes `solutions`, which should all be of one day, returning the first failure.
+pub fn fuzz(
+    solutions: &[&'static Solution],
+    examples: &[String],
+    config: &Config,
+) -> Option<Failure> {
+    // Every caught panic would otherwise print its message and location.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let failure = find_failure(solutions, examples, config);
+    panic::set_hook(hook);
+    failure
+}
+
+fn find_failure(
+    solutions: &[&'static Solution],
+    examples: &[String],
+    config: &Config,
+) -> Option<Failure> {
+    let mut seed = [0; 32];
+    for chunk in seed.chunks_mut(8) {
+        chunk.copy_from_slice(&config.seed.to_le_bytes());
+    }
+    let mut runner = TestRunner::new_with_rng(
+        RunnerConfig::default(),
+        TestRng::from_seed(RngAlgorithm::ChaCha, &seed),
+    );
+    let strategy = inputs(examples);
+
+    let cases = examples
+        .iter()
+        .cloned()
+        .chain((0..config.cases).map(|_| strategy.new_tree(&mut runner).unwrap().current()));
+    for (i, input) in cases.enumerate() {
+        let Some((solution, fault)) = check(solutions, &input, config.timeout) else {
+            continue;
+        };
+        let input = match fault {
+            Fault::Panicked(_) => minimise(&input, |candidate| {
+                matches!(
+                    check(&[solution], candidate, config.timeout),
+                    Some((_, Fault::Panicked(_)))
+                )
+            }),
+            // Shrinking a hang would leave a thread spinning for every attempt.
+            Fault::TimedOut => input,
+        };
+        // Report the panic of the minimised input, which may differ from the original's.
+        let fault = match fault {
+            Fault::Panicked(message) => match check(&[solution], &input, config.timeout) {
+                Some((_, fault @ Fault::Panicked(_))) => fault,
+                _ => Fault::Panicked(message),
+            },
+            Fault::TimedOut => Fault::TimedOut,
+        };
+        return Some(Failure {
+            solution,
+            fault,
+            input,
+            cases: i + 1,
+        });
+    }
+    None
+}
+
+/// Greedily removes lines, then characters, from `input` for as long as it still `fails`,
+/// trying large chunks before small ones.
+pub fn minimise(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let lines = input.split('\n').map(String::from).collect();
+    let lines = remove_chunks(lines, |lines| fails(&lines.join("\n")));
+    let chars = lines.join("\n").chars().map(String::from).collect();
+    remove_chunks(chars, |chars| fails(&chars.concat())).concat()
+}
+
+fn remove_chunks(mut units: Vec<String>, mut fails: impl FnMut(&[String]) -> bool) -> Vec<String> {
+    let mut chunk = units.len().div_ceil(2);
+    while chunk > 0 {
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate = [&units[..start], &units[end..]].concat();
+            if fails(&candidate) {
+                units = candidate;
+            } else {
+                start = end;
+            }
+        }
+        chunk /= 2;
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Borrow, error::Error};
+
+    use aoc_runner::Runner;
+
+    use super::*;
+
+    fn build(input: ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>> {
+        let input: &str = input.borrow();
+        assert!(!input.contains("##"), "two walls in a row");
+        Err("not a real solution".into())
+    }
+
+    static SOLUTION: Solution = Solution {
+        year: 2024,
+        day: 1,
+        part: 1,
+        name: None,
+        build,
+    };
+
+    #[test]
+    fn test_minimise() {
+        assert_eq!(
+            minimise("hello\nxaby\nworld\n", |input| input.contains("ab")),
+            "ab"
+        );
+        assert_eq!(minimise("x", |input| input.contains('y')), "x");
+    }
+
+    #[test]
+    fn test_mutate() {
+        let mutations = [
+            Mutation::Replace(0, '#'),
+            Mutation::DuplicateLine(3),
+            Mutation::Delete(0, 3),
+        ];
+        assert_eq!(mutate("a.\nb.", &mutations), "b.\nb.");
+        assert_eq!(mutate("", &[Mutation::Truncate(0)]), "");
+    }
+
+    #[test]
+    fn test_fuzz_finds_minimised_panic() {
+        let examples = ["#.#.\n.#.#\n#.#.".to_string()];
+        let failure = fuzz(&[&SOLUTION], &examples, &Config::default()).unwrap();
+        assert_eq!(failure.input, "##");
+        assert_eq!(
+            failure.fault,
+            Fault::Panicked("two walls in a row".to_string())
+        );
+        assert!(
+            fuzz(
+                &[&SOLUTION],
+                &[],
+                &Config {
+                    cases: 0,
+                    ..Config::default()
+                }
+            )
+            .is_none()
+        );
+    }
+}
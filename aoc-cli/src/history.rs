@@ -0,0 +1,279 @@
+//! A local log of how long each solution took, appended to by `aoc run` and summarised by
+//! `aoc history`, so that a slowdown shows up against earlier runs on the same machine.
+//!
+//! The log is `perf-history.jsonl` at the workspace root, or `AOC_HISTORY` if it is set, with
+//! one JSON entry per part per run. Runs are labelled with `AOC_MACHINE`, or the host name, as
+//! times from different machines can't be compared.
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    env,
+    fmt::Write as _,
+    fs::{self, OpenOptions},
+    io::Write as _,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    report::{self, Report, Status},
+    workspace_root,
+};
+
+pub fn default_path() -> PathBuf {
+    env::var_os("AOC_HISTORY")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_root().join("perf-history.jsonl"))
+}
+
+/// The machine times are recorded against.
+pub fn machine() -> String {
+    env::var("AOC_MACHINE")
+        .or_else(|_| env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// The short hash of the checked-out commit, if the workspace is a git checkout.
+pub fn commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(workspace_root())
+        .output()
+        .ok()?;
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !hash.is_empty()).then_some(hash)
+}
+
+/// One part's times from one run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub commit: Option<String>,
+    pub machine: String,
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub name: Option<String>,
+    pub generator_ns: u64,
+    pub solver_ns: u64,
+}
+
+impl Entry {
+    /// The entry for a successful run, stamped with the current time.
+    pub fn new(report: &Report, commit: Option<&str>, machine: &str) -> Option<Self> {
+        if report.status != Status::Ok {
+            return None;
+        }
+        Some(Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_secs()),
+            commit: commit.map(String::from),
+            machine: machine.to_string(),
+            year: report.year,
+            day: report.day,
+            part: report.part,
+            name: report.name.map(String::from),
+            generator_ns: report.generator_time?.as_nanos() as u64,
+            solver_ns: report.solver_time?.as_nanos() as u64,
+        })
+    }
+
+    pub fn total(&self) -> Duration {
+        Duration::from_nanos(self.generator_ns + self.solver_ns)
+    }
+
+    fn key(&self) -> (u32, u8, u8, Option<&str>) {
+        (self.year, self.day, self.part, self.name.as_deref())
+    }
+}
+
+pub fn append(path: &Path, entries: &[Entry]) -> Result<(), String> {
+    let error = |e: std::io::Error| format!("failed to write {}: {e}", path.display());
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(error)?;
+    let mut lines = String::new();
+    for entry in entries {
+        writeln!(lines, "{}", serde_json::to_string(entry).unwrap()).unwrap();
+    }
+    file.write_all(lines.as_bytes()).map_err(error)
+}
+
+/// Reads the log, which is empty if there isn't one yet.
+pub fn load(path: &Path) -> Result<Vec<Entry>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+    };
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| format!("{}:{}: {e}", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// How one part's times have moved over its runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trend {
+    pub label: String,
+    pub runs: usize,
+    pub best: Duration,
+    pub previous: Option<Duration>,
+    pub latest: Duration,
+    pub commit: Option<String>,
+}
+
+impl Trend {
+    /// The latest time relative to the one before it, e.g. `1.5` for half as slow again.
+    pub fn change(&self) -> Option<f64> {
+        self.previous
+            .map(|previous| self.latest.as_secs_f64() / previous.as_secs_f64())
+    }
+}
+
+/// The trend of each part that has entries, slowest first by its latest time. Entries are taken
+/// to be in the order they were recorded.
+pub fn trends<'a>(entries: impl IntoIterator<Item = &'a Entry>) -> Vec<Trend> {
+    let mut by_part: BTreeMap<_, Vec<&Entry>> = BTreeMap::new();
+    for entry in entries {
+        by_part.entry(entry.key()).or_default().push(entry);
+    }
+
+    let mut trends: Vec<Trend> = by_part
+        .into_iter()
+        .map(|((year, day, part, name), runs)| {
+            let latest = runs[runs.len() - 1];
+            Trend {
+                label: report::label(year, day, part, name),
+                runs: runs.len(),
+                best: runs.iter().map(|entry| entry.total()).min().unwrap(),
+                previous: runs.len().checked_sub(2).map(|i| runs[i].total()),
+                latest: latest.total(),
+                commit: latest.commit.clone(),
+            }
+        })
+        .collect();
+    trends.sort_by_key(|trend| Reverse(trend.latest));
+    trends
+}
+
+/// A table of trends, in the order given.
+pub fn table(trends: &[Trend]) -> String {
+    let header = [
+        "part", "runs", "best", "previous", "latest", "change", "commit",
+    ];
+    let rows: Vec<[String; 7]> = trends
+        .iter()
+        .map(|trend| {
+            [
+                trend.label.clone(),
+                trend.runs.to_string(),
+                format!("{:.2?}", trend.best),
+                trend
+                    .previous
+                    .map_or_else(|| "-".to_string(), |time| format!("{time:.2?}")),
+                format!("{:.2?}", trend.latest),
+                trend.change().map_or_else(
+                    || "-".to_string(),
+                    |change| format!("{:+.0}%", (change - 1.0) * 100.0),
+                ),
+                trend.commit.clone().unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    report::table(header, &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, part: u8, millis: u64, commit: &str) -> Entry {
+        Entry {
+            timestamp: 0,
+            commit: Some(commit.to_string()),
+            machine: "test".to_string(),
+            year: 2024,
+            day,
+            part,
+            name: None,
+            generator_ns: 1_000_000,
+            solver_ns: (millis - 1) * 1_000_000,
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(load(&path), Ok(Vec::new()));
+
+        let entries = [entry(1, 1, 5, "abc1234"), entry(1, 2, 8, "abc1234")];
+        append(&path, &entries[..1]).unwrap();
+        append(&path, &entries[1..]).unwrap();
+        let loaded = load(&path);
+        fs::write(&path, "{}\n").unwrap();
+        let broken = load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, Ok(entries.to_vec()));
+        assert!(broken.unwrap_err().contains(":1: missing field"));
+    }
+
+    #[test]
+    fn test_trends() {
+        let entries = [
+            entry(1, 1, 10, "a"),
+            entry(6, 2, 400, "a"),
+            entry(1, 1, 8, "b"),
+            entry(1, 1, 12, "c"),
+        ];
+        let trends = trends(&entries);
+        assert_eq!(
+            trends,
+            [
+                Trend {
+                    label: "2024 day 6 part 2".to_string(),
+                    runs: 1,
+                    best: Duration::from_millis(400),
+                    previous: None,
+                    latest: Duration::from_millis(400),
+                    commit: Some("a".to_string()),
+                },
+                Trend {
+                    label: "2024 day 1 part 1".to_string(),
+                    runs: 3,
+                    best: Duration::from_millis(8),
+                    previous: Some(Duration::from_millis(8)),
+                    latest: Duration::from_millis(12),
+                    commit: Some("c".to_string()),
+                },
+            ]
+        );
+        assert_eq!(trends[1].change(), Some(1.5));
+
+        let table = table(&trends);
+        let mut lines = table.lines();
+        assert!(lines.next().unwrap().starts_with("part"));
+        assert!(lines.next().unwrap().starts_with("2024 day 6 part 2  1"));
+        let last = lines.next().unwrap().split_whitespace().collect::<Vec<_>>();
+        assert_eq!(last[last.len() - 3..], ["12.00ms", "+50%", "c"]);
+    }
+}
@@ -0,0 +1,21 @@
+//! Discovery and bookkeeping for the solutions of every year, shared by the `aoc` binary and the
+//! workspace-wide tests.
+
+use std::path::Path;
+
+pub mod answers;
+pub mod budget;
+pub mod client;
+pub mod compare;
+pub mod fuzz;
+pub mod history;
+pub mod manifest;
+pub mod mock;
+pub mod report;
+pub mod scaffold;
+pub mod solutions;
+
+/// The root of the workspace, where each year's crate and `answers.toml` live.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
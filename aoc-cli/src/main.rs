@@ -0,0 +1,436 @@
+//! `aoc`: runs the solutions of every year from the command line.
+
+mod args;
+
+use std::{
+    env,
+    io::{self, Read},
+    panic,
+    path::Path,
+    process::ExitCode,
+    thread,
+};
+
+use aoc_cli::{
+    client::{Client, Verdict},
+    compare,
+    fuzz::{self, Fault},
+    history::{self, Entry},
+    report::{self, Report, Status},
+    scaffold,
+    solutions::{self, SOLUTIONS, Solution},
+};
+use aoc_core::{
+    diag::{self, Level},
+    input::normalize,
+    player::{Player, Simulation},
+    render::FrameWriter,
+};
+use aoc_runner::ArcStr;
+use args::{
+    Command, ExportArgs, FetchArgs, FuzzArgs, GenerateArgs, HistoryArgs, Input, NewArgs, PlayArgs,
+    RunArgs, SubmitArgs,
+};
+use itertools::Itertools;
+
+fn main() -> ExitCode {
+    let command = match args::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{}", args::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    // Whether the command succeeded, e.g. every solution ran or the answer was right.
+    let result = match command {
+        Command::Run(args) => run(&args),
+        Command::Play(args) => play(&args).map(|()| true),
+        Command::Export(args) => export(&args).map(|()| true),
+        Command::Generate(args) => generate(&args).map(|()| true),
+        Command::Fuzz(args) => fuzz(&args),
+        Command::History(args) => show_history(&args).map(|()| true),
+        Command::New(args) => new(&args).map(|()| true),
+        Command::Fetch(args) => fetch(&args).map(|()| true),
+        Command::Submit(args) => submit(&args),
+        Command::Help => {
+            println!("{}", args::USAGE);
+            Ok(true)
+        }
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The solutions of a year, or of one of its days or parts, in day order.
+fn select(year: u32, day: Option<u8>, part: Option<u8>) -> Result<Vec<&'static Solution>, String> {
+    let selected = SOLUTIONS
+        .iter()
+        .filter(|s| s.year == year)
+        .filter(|s| day.is_none_or(|day| s.day == day))
+        .filter(|s| part.is_none_or(|part| s.part == part))
+        .collect_vec();
+
+    if selected.is_empty() {
+        return Err(match (day, part) {
+            (None, _) => format!("no solutions for {year}"),
+            (Some(day), None) => format!("no solutions for {year} day {day}"),
+            (Some(day), Some(part)) => format!("no solution for {year} day {day} part {part}"),
+        });
+    }
+    Ok(selected)
+}
+
+/// Runs every selected solution, returning whether all of them succeeded.
+fn run(args: &RunArgs) -> Result<bool, String> {
+    diag::set_level(args.diagnostics);
+
+    let selected = select(args.year, args.day, args.part)?;
+
+    if let Some(dir) = &args.inputs {
+        let runs = compare::run(&selected, dir)?;
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&runs).unwrap());
+        } else {
+            println!("{}", compare::table(&runs));
+        }
+        return Ok(runs.iter().all(|run| run.is_ok()));
+    }
+
+    let days = selected
+        .into_iter()
+        .chunk_by(|s| s.day)
+        .into_iter()
+        .map(|(day, solutions)| Ok((read_input(args, day)?, solutions.collect_vec())))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let reports = if args.parallel {
+        let reports = run_days_concurrently(&days);
+        if !args.json {
+            reports.iter().for_each(print_report);
+        }
+        reports
+    } else {
+        let mut reports = Vec::new();
+        for (input, solutions) in &days {
+            for solution in solutions {
+                let report = report::run(solution, input);
+                if !args.json {
+                    print_report(&report);
+                }
+                reports.push(report);
+            }
+        }
+        reports
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+    }
+    // Only times on the checked-in inputs, without other days competing or diagnostics being
+    // written, are comparable.
+    if args.input.is_none() && !args.parallel && args.diagnostics == Level::Off {
+        record_history(&reports);
+    }
+    Ok(reports.iter().all(|report| report.status == Status::Ok))
+}
+
+/// Appends the successful reports' times to the history, warning rather than failing the run if
+/// it can't.
+fn record_history(reports: &[Report]) {
+    let (commit, machine) = (history::commit(), history::machine());
+    let entries = reports
+        .iter()
+        .filter_map(|report| Entry::new(report, commit.as_deref(), &machine))
+        .collect_vec();
+    if let Err(error) = history::append(&history::default_path(), &entries) {
+        eprintln!("warning: {error}");
+    }
+}
+
+/// Runs each day's parts in order on a thread of its own, returning the reports in day order.
+fn run_days_concurrently(days: &[(ArcStr, Vec<&Solution>)]) -> Vec<Report> {
+    thread::scope(|scope| {
+        let handles = days
+            .iter()
+            .map(|(input, solutions)| {
+                scope.spawn(move || {
+                    solutions
+                        .iter()
+                        .map(|solution| report::run(solution, input))
+                        .collect_vec()
+                })
+            })
+            .collect_vec();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+fn read_input(args: &RunArgs, day: u8) -> Result<ArcStr, String> {
+    let input = match &args.input {
+        Some(Input::Stdin) => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("failed to read stdin: {e}"))?;
+            normalize(&input).into_owned()
+        }
+        Some(Input::File(path)) => solutions::read_input(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?,
+        None => {
+            let path = solutions::default_input(args.year, day);
+            solutions::read_input(&path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?
+        }
+    };
+    Ok(ArcStr::from(&input))
+}
+
+fn play(args: &PlayArgs) -> Result<(), String> {
+    let PlayArgs {
+        year, day, part, ..
+    } = *args;
+
+    let input = read_simulation_input(year, day, args.input.as_deref())?;
+    let input = input.as_str();
+
+    // Build it once up front so a missing simulation or bad input is reported before playing.
+    simulation(year, day, part, input, "play")?;
+
+    Player::new()
+        .fps(args.fps)
+        .paused(args.paused)
+        .play(|| {
+            solutions::simulation(year, day, part, input)
+                .unwrap()
+                .unwrap()
+        })
+        .map_err(|e| e.to_string())
+}
+
+fn export(args: &ExportArgs) -> Result<(), String> {
+    let ExportArgs {
+        year, day, part, ..
+    } = *args;
+
+    let input = read_simulation_input(year, day, args.input.as_deref())?;
+    let mut sim = simulation(year, day, part, &input, "export")?;
+    if sim.canvas().is_none() {
+        return Err(format!(
+            "{year} day {day} part {part} can't be drawn as an image"
+        ));
+    }
+
+    for frame in 0..args.frame {
+        if !sim.step() {
+            return Err(format!("the simulation finished after {frame} frames"));
+        }
+    }
+
+    let mut writer = FrameWriter::new(&args.out, args.format, args.scale)
+        .map_err(|e| format!("failed to create {}: {e}", args.out.display()))?;
+    for i in 0..args.frames {
+        let path = writer
+            .write(sim.canvas().unwrap())
+            .map_err(|e| format!("failed to write frame: {e}"))?;
+        println!("{}", path.display());
+        if i + 1 < args.frames && !sim.step() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn generate(args: &GenerateArgs) -> Result<(), String> {
+    let GenerateArgs {
+        year,
+        day,
+        size,
+        seed,
+    } = *args;
+
+    let input = solutions::generate_input(year, day, size, seed)
+        .ok_or_else(|| format!("{year} day {day} has no input generator"))?;
+    println!("{input}");
+    Ok(())
+}
+
+/// Fuzzes each selected day in turn, returning whether none of them panicked or hung. A day that
+/// hangs is reported like one that panics, and its solver is left running on its own thread while
+/// the next day is fuzzed.
+fn fuzz(args: &FuzzArgs) -> Result<bool, String> {
+    let FuzzArgs {
+        year, day, part, ..
+    } = *args;
+    let config = &args.config;
+
+    let mut ok = true;
+    for (day, solutions) in &select(year, day, part)?.into_iter().chunk_by(|s| s.day) {
+        let solutions = solutions.collect_vec();
+        let examples = fuzz::examples(year, day, config);
+
+        let Some(failure) = fuzz::fuzz(&solutions, &examples, config) else {
+            println!(
+                "{year} day {day}: no failures in {} inputs",
+                examples.len() + config.cases
+            );
+            continue;
+        };
+        ok = false;
+
+        let solution = failure.solution;
+        let name = solution
+            .name
+            .map_or_else(String::new, |name| format!(" ({name})"));
+        let fault = match &failure.fault {
+            Fault::Panicked(message) => format!("panicked: {message}"),
+            Fault::TimedOut => format!("took longer than {:?}", config.timeout),
+        };
+        println!(
+            "{year} day {day} part {}{name} {fault}\n  on input {} of {}: {:?}",
+            solution.part,
+            failure.cases,
+            examples.len() + config.cases,
+            failure.input
+        );
+    }
+    Ok(ok)
+}
+
+fn show_history(args: &HistoryArgs) -> Result<(), String> {
+    let path = history::default_path();
+    let machine = history::machine();
+    let entries = history::load(&path)?;
+    let trends = history::trends(entries.iter().filter(|entry| {
+        entry.machine == machine
+            && args.year.is_none_or(|year| entry.year == year)
+            && args.day.is_none_or(|day| entry.day == day)
+            && args.part.is_none_or(|part| entry.part == part)
+    }));
+
+    if trends.is_empty() {
+        println!(
+            "no times recorded for {machine} in {}; 'aoc run' records them",
+            path.display()
+        );
+    } else {
+        print!("{}", history::table(&trends));
+    }
+    Ok(())
+}
+
+fn new(args: &NewArgs) -> Result<(), String> {
+    let scaffold = scaffold::new_day(aoc_cli::workspace_root(), args.year, args.day)
+        .map_err(|e| e.to_string())?;
+    println!("created {}", scaffold.solution.display());
+    println!("updated {}", scaffold.lib.display());
+    match scaffold.input {
+        Some(input) => println!("created {} (empty)", input.display()),
+        None => println!("kept the existing input"),
+    }
+    Ok(())
+}
+
+/// A client for the site, or the server in `AOC_BASE_URL`, logged in with `AOC_SESSION`.
+fn client() -> Result<Client, String> {
+    let session = env::var("AOC_SESSION")
+        .map_err(|_| "set AOC_SESSION to your adventofcode.com session cookie".to_string())?;
+    let client = Client::new(session);
+    Ok(match env::var("AOC_BASE_URL") {
+        Ok(base_url) => client.base_url(base_url),
+        Err(_) => client,
+    })
+}
+
+fn fetch(args: &FetchArgs) -> Result<(), String> {
+    let mut client = client()?;
+    client
+        .input(args.year, args.day)
+        .map_err(|e| e.to_string())?;
+    println!(
+        "{}",
+        client.cached_input_path(args.year, args.day).display()
+    );
+    Ok(())
+}
+
+/// Submits the answer, returning whether it was right.
+fn submit(args: &SubmitArgs) -> Result<bool, String> {
+    let SubmitArgs {
+        year, day, part, ..
+    } = *args;
+    let mut client = client()?;
+
+    let answer = match &args.answer {
+        Some(answer) => answer.clone(),
+        None => {
+            // Submit the main solution's answer rather than an alternative's.
+            let solution = select(year, Some(day), Some(part))?
+                .into_iter()
+                .find(|s| s.name.is_none())
+                .ok_or_else(|| format!("no solution for {year} day {day} part {part}"))?;
+            let input = client.input(year, day).map_err(|e| e.to_string())?;
+            let report = report::run(solution, &ArcStr::from(&normalize(&input)));
+            match report.answer {
+                Some(answer) => answer.to_string(),
+                None => return Err(format!("{}: {}", report.label(), report.error.unwrap())),
+            }
+        }
+    };
+
+    println!("submitting {answer} for {year} day {day} part {part}");
+    let verdict = client
+        .submit(year, day, part, &answer)
+        .map_err(|e| e.to_string())?;
+    println!("{verdict}");
+    Ok(verdict == Verdict::Correct)
+}
+
+/// The day's simulation on `input`, for the command that will `verb` it.
+fn simulation(
+    year: u32,
+    day: u8,
+    part: u8,
+    input: &str,
+    verb: &str,
+) -> Result<Box<dyn Simulation>, String> {
+    solutions::simulation(year, day, part, input)
+        .ok_or_else(|| format!("{year} day {day} part {part} has no simulation to {verb}"))?
+        .map_err(|e| e.to_string())
+}
+
+fn read_simulation_input(year: u32, day: u8, input: Option<&Path>) -> Result<String, String> {
+    let path = input.map_or_else(|| solutions::default_input(year, day), Path::to_path_buf);
+    solutions::read_input(&path).map_err(|e| format!("failed to read {}: {e}", path.display()))
+}
+
+fn print_report(report: &Report) {
+    let label = report.label();
+    let error = report.error.as_deref().unwrap_or_default();
+    match report.status {
+        Status::Ok => println!(
+            "{label}: {}\n\tgenerator: {:?}\n\tsolver: {:?}\n",
+            report.answer.as_ref().unwrap(),
+            report.generator_time.unwrap(),
+            report.solver_time.unwrap()
+        ),
+        Status::GeneratorFailed => println!("{label}: generator failed\n{error}\n"),
+        Status::SolverFailed => println!("{label}: solver failed\n{error}\n"),
+        Status::Panicked => println!("{label}: panicked\n{error}\n"),
+    }
+}
@@ -0,0 +1,80 @@
+//! The subset of TOML that `answers.toml` and `budgets.toml` are written in: `[section]` headers,
+//! `key = value` lines and `#` comments. Values are left as text for each file to interpret, so
+//! that an answer too big for any integer type is kept exactly as written.
+
+/// Calls `entry` with the section, key and value of each `key = value` line, where the section is
+/// `None` before the first header. Headers are read by `header`, which `header_format`
+/// describes. Errors, whether found here or returned by `entry`, are reported as `line: message`.
+pub fn parse<S>(
+    input: &str,
+    header_format: &str,
+    header: impl Fn(&str) -> Option<S>,
+    mut entry: impl FnMut(Option<&S>, &str, &str) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut section = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let error = |message: &str| format!("{}: {message}", i + 1);
+
+        let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = Some(
+                header(name.trim())
+                    .ok_or_else(|| error(&format!("expected a {header_format} header")))?,
+            );
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected <key> = <value>"))?;
+        entry(section.as_ref(), key.trim(), value.trim()).map_err(|message| error(&message))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let mut entries = Vec::new();
+        parse(
+            "# comment\ntop = 1\n\n[ a ]\nx = \"y\" # trailing\n",
+            "[<name>]",
+            |name| Some(name.to_string()),
+            |section, key, value| {
+                entries.push((section.cloned(), key.to_string(), value.to_string()));
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            entries,
+            [
+                (None, "top".to_string(), "1".to_string()),
+                (Some("a".to_string()), "x".to_string(), "\"y\"".to_string()),
+            ]
+        );
+
+        let reject = |_: Option<&()>, _: &str, _: &str| Err("no".to_string());
+        assert_eq!(
+            parse("\n\nx = 1", "", |_| Some(()), reject),
+            Err("3: no".to_string())
+        );
+        assert_eq!(
+            parse("[b]", "[a]", |_| None::<()>, reject),
+            Err("1: expected a [a] header".to_string())
+        );
+        assert_eq!(
+            parse("x", "", |_| Some(()), reject),
+            Err("1: expected <key> = <value>".to_string())
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! A local stand-in for the Advent of Code endpoints the [client](crate::client) uses, so that
+//! fetching and submitting can be tested offline. Its pages copy the wording of the real site's
+//! closely enough for [`parse_verdict`](crate::client::parse_verdict) to read them.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// What the server knows before it starts: the session it accepts, and each day's input and
+/// answers.
+#[derive(Debug, Clone)]
+pub struct MockConfig {
+    session: String,
+    inputs: HashMap<(u32, u8), String>,
+    answers: HashMap<(u32, u8, u8), String>,
+    cooldown: Duration,
+}
+
+impl MockConfig {
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            session: session.into(),
+            inputs: HashMap::new(),
+            answers: HashMap::new(),
+            cooldown: Duration::from_secs(60),
+        }
+    }
+
+    pub fn input(mut self, year: u32, day: u8, input: impl Into<String>) -> Self {
+        self.inputs.insert((year, day), input.into());
+        self
+    }
+
+    pub fn answer(mut self, year: u32, day: u8, part: u8, answer: impl Into<String>) -> Self {
+        self.answers.insert((year, day, part), answer.into());
+        self
+    }
+
+    /// How long a wrong answer locks out further answers.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Starts serving on a free local port, until the returned server is dropped.
+    pub fn start(self) -> io::Result<MockServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            config: self,
+            solved: HashSet::new(),
+            locked_until: None,
+            requests: Vec::new(),
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = thread::spawn({
+            let state = Arc::clone(&state);
+            let shutdown = Arc::clone(&shutdown);
+            move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    // A client hanging up early only affects its own request.
+                    if let Ok(stream) = stream {
+                        let _ = handle_connection(stream, &state);
+                    }
+                }
+            }
+        });
+
+        Ok(MockServer {
+            addr,
+            state,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+}
+
+struct State {
+    config: MockConfig,
+    solved: HashSet<(u32, u8, u8)>,
+    locked_until: Option<Instant>,
+    requests: Vec<String>,
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// The base URL to give the client, e.g. `http://127.0.0.1:41234`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Every request so far, as `GET /2024/day/1/input`.
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    body: String,
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let request = read_request(&mut reader)?;
+    let (status, body) = respond(&request, &mut state.lock().unwrap());
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Method Not Allowed",
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn read_request(reader: &mut impl BufRead) -> io::Result<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut cookie = None;
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.trim_end().split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => {
+                cookie = value
+                    .split(';')
+                    .find_map(|cookie| cookie.trim().strip_prefix("session="))
+                    .map(String::from);
+            }
+            "content-length" => content_length = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn respond(request: &Request, state: &mut State) -> (u16, String) {
+    state
+        .requests
+        .push(format!("{} {}", request.method, request.path));
+
+    if request.cookie.as_deref() != Some(state.config.session.as_str()) {
+        return (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".into(),
+        );
+    }
+
+    let segments: Vec<_> = request.path.trim_matches('/').split('/').collect();
+    let (year, day, endpoint) = match segments[..] {
+        [year, "day", day, endpoint] => match (year.parse(), day.parse()) {
+            (Ok(year), Ok(day)) => (year, day, endpoint),
+            _ => return not_found(),
+        },
+        _ => return not_found(),
+    };
+
+    match (request.method.as_str(), endpoint) {
+        ("GET", "input") => match state.config.inputs.get(&(year, day)) {
+            Some(input) => (200, format!("{input}\n")),
+            None => not_found(),
+        },
+        ("POST", "answer") => submit(state, year, day, &form_values(&request.body)),
+        _ => (405, String::new()),
+    }
+}
+
+fn not_found() -> (u16, String) {
+    (
+        404,
+        "Please don't repeatedly request this endpoint before it unlocks!\n".into(),
+    )
+}
+
+fn submit(state: &mut State, year: u32, day: u8, form: &HashMap<String, String>) -> (u16, String) {
+    let part = form.get("level").and_then(|level| level.parse().ok());
+    let (Some(part), Some(answer)) = (part, form.get("answer")) else {
+        return (400, String::new());
+    };
+    let Some(expected) = state.config.answers.get(&(year, day, part)) else {
+        return not_found();
+    };
+
+    let now = Instant::now();
+    if let Some(wait) = state
+        .locked_until
+        .and_then(|until| until.checked_duration_since(now))
+    {
+        let seconds = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        let wait = match (seconds / 60, seconds % 60) {
+            (0, s) => format!("{s}s"),
+            (m, s) => format!("{m}m {s}s"),
+        };
+        return page(&format!(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {wait} left to wait."
+        ));
+    }
+
+    let solved_earlier = (1..part).all(|earlier| state.solved.contains(&(year, day, earlier)));
+    if state.solved.contains(&(year, day, part)) || !solved_earlier {
+        return page("You don't seem to be solving the right level.  Did you already complete it?");
+    }
+
+    if answer.trim() == expected {
+        state.solved.insert((year, day, part));
+        return page("That's the right answer!  You are one gold star closer to finishing.");
+    }
+
+    state.locked_until = Some(now + state.config.cooldown);
+    let hint = match (answer.trim().parse::<i128>(), expected.parse::<i128>()) {
+        (Ok(answer), Ok(expected)) if answer > expected => "; your answer is too high",
+        (Ok(answer), Ok(expected)) if answer < expected => "; your answer is too low",
+        _ => "",
+    };
+    page(&format!(
+        "That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.  Please wait before trying again."
+    ))
+}
+
+fn page(message: &str) -> (u16, String) {
+    (
+        200,
+        format!(
+            "<!DOCTYPE html>\n<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>\n"
+        ),
+    )
+}
+
+/// Decodes an `application/x-www-form-urlencoded` body.
+fn form_values(body: &str) -> HashMap<String, String> {
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(name, value)| (percent_decode(name), percent_decode(value)))
+        .collect()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| str::from_utf8(hex).ok());
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_values() {
+        let form = form_values("level=2&answer=a+b%21");
+        assert_eq!(form["level"], "2");
+        assert_eq!(form["answer"], "a b!");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+}
@@ -0,0 +1,183 @@
+//! Runs a solution and records what happened, for printing as text or as a JSON report.
+
+use std::{
+    any::Any,
+    fmt::Write,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use aoc_core::answer::Answer;
+use aoc_runner::ArcStr;
+use serde::{Serialize, Serializer};
+
+use crate::solutions::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    GeneratorFailed,
+    SolverFailed,
+    /// The generator or the solver panicked.
+    Panicked,
+}
+
+/// The outcome of running one solution. Times are reported in nanoseconds.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub name: Option<&'static str>,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    #[serde(rename = "generator_ns", serialize_with = "nanos")]
+    pub generator_time: Option<Duration>,
+    #[serde(rename = "solver_ns", serialize_with = "nanos")]
+    pub solver_time: Option<Duration>,
+    pub error: Option<String>,
+}
+
+fn nanos<S: Serializer>(time: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    time.map(|time| time.as_nanos() as u64)
+        .serialize(serializer)
+}
+
+impl Report {
+    /// E.g. `2024 day 15 part 2`, with the name of alternative solutions in brackets.
+    pub fn label(&self) -> String {
+        label(self.year, self.day, self.part, self.name)
+    }
+}
+
+/// The label of a part, as in [`Report::label`].
+pub fn label(year: u32, day: u8, part: u8, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{year} day {day} part {part} ({name})"),
+        None => format!("{year} day {day} part {part}"),
+    }
+}
+
+/// Lays rows out in columns under a header, two spaces apart.
+pub fn table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let widths: Vec<usize> = (0..N)
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([header[i].chars().count()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut table = String::new();
+    for row in [header.map(String::from)].iter().chain(rows) {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect();
+        writeln!(table, "{}", cells.join("  ").trim_end()).unwrap();
+    }
+    table
+}
+
+pub fn run(solution: &Solution, input: &ArcStr) -> Report {
+    let mut report = Report {
+        year: solution.year,
+        day: solution.day,
+        part: solution.part,
+        name: solution.name,
+        status: Status::Ok,
+        answer: None,
+        generator_time: None,
+        solver_time: None,
+        error: None,
+    };
+
+    let start = Instant::now();
+    let runner = match panic::catch_unwind(AssertUnwindSafe(|| (solution.build)(input.clone()))) {
+        Ok(Ok(runner)) => runner,
+        Ok(Err(error)) => {
+            report.status = Status::GeneratorFailed;
+            report.error = Some(error.to_string());
+            return report;
+        }
+        Err(panic) => {
+            report.status = Status::Panicked;
+            report.error = Some(panic_message(&*panic));
+            return report;
+        }
+    };
+    let generated = Instant::now();
+    report.generator_time = Some(generated - start);
+
+    let answer = match panic::catch_unwind(AssertUnwindSafe(|| runner.try_run())) {
+        Ok(answer) => answer,
+        Err(panic) => {
+            report.status = Status::Panicked;
+            report.error = Some(panic_message(&*panic));
+            return report;
+        }
+    };
+    match answer {
+        Ok(answer) => {
+            report.solver_time = Some(generated.elapsed());
+            report.answer = Some(answer.to_string().parse().unwrap());
+        }
+        Err(error) => {
+            report.status = Status::SolverFailed;
+            report.error = Some(error.to_string());
+        }
+    }
+    report
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions::SOLUTIONS;
+
+    fn find(year: u32, day: u8, part: u8) -> &'static Solution {
+        SOLUTIONS
+            .iter()
+            .find(|s| (s.year, s.day, s.part) == (year, day, part))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_json() {
+        let report = run(find(2024, 1, 1), &ArcStr::from("3   4\n4   3\n2   5\n"));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["answer"], 3);
+        assert!(json["generator_ns"].is_u64());
+        assert!(json["solver_ns"].is_u64());
+        assert!(json["error"].is_null());
+    }
+
+    #[test]
+    fn test_generator_failure() {
+        let report = run(find(2024, 1, 1), &ArcStr::from("not a list"));
+        assert_eq!(report.status, Status::GeneratorFailed);
+        assert_eq!(report.answer, None);
+        assert!(report.error.is_some());
+    }
+
+    #[test]
+    fn test_panic() {
+        // The guard walks in a loop, so part 1's patrol never leaves the map.
+        let report = run(find(2024, 6, 1), &ArcStr::from(".#..\n...#\n#^..\n..#."));
+        assert_eq!(report.status, Status::Panicked);
+        assert!(report.error.unwrap().contains("unwrap"));
+    }
+}
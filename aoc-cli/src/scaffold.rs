@@ -0,0 +1,218 @@
+//! Starts a new day: writes a solution file from a template, registers its module in the year's
+//! `lib.rs`, and adds an empty input file to paste or fetch the real input into.
+//!
+//! The build script finds the new day's `#[aoc]` attributes by itself, so nothing else needs
+//! editing before `aoc run` picks it up.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// The files [`new_day`] touched.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Scaffold {
+    pub solution: PathBuf,
+    pub lib: PathBuf,
+    /// `None` if the input was already there, e.g. from `aoc fetch`, and left alone.
+    pub input: Option<PathBuf>,
+}
+
+/// Adds `day` to the `year` crate under `root`, refusing if the crate already has that day.
+/// Everything is checked before anything is written. The input is created first, as an empty file
+/// is harmless to leave behind, but a failure writing `lib.rs` still leaves the solution written.
+pub fn new_day(root: &Path, year: u32, day: u8) -> io::Result<Scaffold> {
+    let crate_dir = root.join(year.to_string());
+    let manifest = fs::read_to_string(crate_dir.join("Cargo.toml")).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("no crate for {year} at {}", crate_dir.display()),
+        )
+    })?;
+    let src = crate_dir.join("src");
+    let solution = src.join(format!("day{day}.rs"));
+    let lib = src.join("lib.rs");
+
+    if solution.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", solution.display()),
+        ));
+    }
+    let registered = register(&fs::read_to_string(&lib)?, day).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already declares day{day}", lib.display()),
+        )
+    })?;
+
+    let input = crate_dir.join(format!("input/{year}/day{day}.txt"));
+    fs::create_dir_all(input.parent().unwrap())?;
+    let input = match OpenOptions::new().write(true).create_new(true).open(&input) {
+        Ok(_) => Some(input),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => None,
+        Err(e) => return Err(e),
+    };
+
+    let edition_2021 = manifest.contains("edition = \"2021\"");
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&solution)?
+        .write_all(template(day, edition_2021).as_bytes())?;
+    fs::write(&lib, registered)?;
+
+    Ok(Scaffold {
+        solution,
+        lib,
+        input,
+    })
+}
+
+/// `lib` with `mod day<day>;` added among the other days' modules, which are kept in plain
+/// string order (so `day10` comes before `day2`). `None` if it's already there.
+fn register(lib: &str, day: u8) -> Option<String> {
+    let line = format!("mod day{day};");
+    let mut lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&line.as_str()) {
+        return None;
+    }
+
+    let is_day = |l: &&str| l.starts_with("mod day");
+    // Compare names rather than lines, as `;` sorts after the digits.
+    let name = |l: &str| {
+        l.trim_start_matches("mod ")
+            .trim_end_matches(';')
+            .to_string()
+    };
+    let at = match lines
+        .iter()
+        .position(|l| is_day(l) && name(l) > format!("day{day}"))
+    {
+        Some(at) => at,
+        None => match lines.iter().rposition(is_day) {
+            Some(last) => last + 1,
+            // The first day of a year: put it just before the solutions are collected.
+            None => {
+                let at = lines.iter().position(|l| l.starts_with("aoc_lib!"))?;
+                lines.insert(at, "");
+                at
+            }
+        },
+    };
+    lines.insert(at, &line);
+
+    let mut registered = lines.join("\n");
+    if lib.ends_with('\n') {
+        registered.push('\n');
+    }
+    Some(registered)
+}
+
+/// A solution that parses one number per line, with example tests, ready to be filled in. The
+/// 2021 edition's rustfmt orders imports differently.
+fn template(day: u8, edition_2021: bool) -> String {
+    let parse = if edition_2021 {
+        "parse_puzzle, PuzzleParseError"
+    } else {
+        "PuzzleParseError, parse_puzzle"
+    };
+    format!(
+        r#"use aoc_core::{{
+    combinators::lines,
+    parse::{{{parse}}},
+}};
+use aoc_runner_derive::{{aoc, aoc_generator}};
+use nom::character::complete::i64;
+
+#[aoc_generator(day{day})]
+pub fn input_generator(input: &str) -> Result<Vec<i64>, PuzzleParseError> {{
+    parse_puzzle({day}, input, lines(i64))
+}}
+
+#[aoc(day{day}, part1)]
+pub fn solve_part1(input: &[i64]) -> i64 {{
+    input.iter().sum()
+}}
+
+#[aoc(day{day}, part2)]
+pub fn solve_part2(input: &[i64]) -> i64 {{
+    input.iter().product()
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const EXAMPLE: &str = r"1
+2
+3";
+
+    #[test]
+    fn test_part1() {{
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(solve_part1(&input), 6);
+    }}
+
+    #[test]
+    fn test_part2() {{
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(solve_part2(&input), 6);
+    }}
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let lib =
+            "use x;\n\nmod day1;\nmod day10;\nmod day2;\nmod day9;\n\naoc_lib! { year = 2024 }\n";
+        assert_eq!(
+            register(lib, 16).unwrap(),
+            "use x;\n\nmod day1;\nmod day10;\nmod day16;\nmod day2;\nmod day9;\n\naoc_lib! { year = 2024 }\n"
+        );
+        assert_eq!(
+            register(lib, 3).unwrap(),
+            "use x;\n\nmod day1;\nmod day10;\nmod day2;\nmod day3;\nmod day9;\n\naoc_lib! { year = 2024 }\n"
+        );
+        assert_eq!(register(lib, 10), None);
+        assert_eq!(
+            register("use x;\n\naoc_lib! { year = 2026 }\n", 1).unwrap(),
+            "use x;\n\nmod day1;\n\naoc_lib! { year = 2026 }\n"
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("2026/src")).unwrap();
+        fs::write(root.join("2026/Cargo.toml"), "edition = \"2024\"\n").unwrap();
+        fs::write(
+            root.join("2026/src/lib.rs"),
+            "mod day1;\n\naoc_lib! { year = 2026 }\n",
+        )
+        .unwrap();
+
+        let scaffold = new_day(&root, 2026, 2).unwrap();
+        let solution = fs::read_to_string(&scaffold.solution).unwrap();
+        let lib = fs::read_to_string(&scaffold.lib).unwrap();
+        let input = fs::read_to_string(scaffold.input.as_ref().unwrap()).unwrap();
+        let again = new_day(&root, 2026, 2).unwrap_err();
+        let missing = new_day(&root, 2027, 1).unwrap_err();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(solution.contains("#[aoc(day2, part2)]"));
+        assert!(solution.contains("parse_puzzle(2, input, lines(i64))"));
+        assert_eq!(lib, "mod day1;\nmod day2;\n\naoc_lib! { year = 2026 }\n");
+        assert_eq!(input, "");
+        assert_eq!(again.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+}
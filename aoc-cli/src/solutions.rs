@@ -0,0 +1,88 @@
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_core::{input::normalize, parse::PuzzleParseError, player::Simulation};
+use aoc_runner::{ArcStr, Runner};
+
+use crate::workspace_root;
+
+/// Runs a day's generator, returning a runner that solves one part.
+pub type Build = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// A single `#[aoc(dayN, partM)]` function.
+pub struct Solution {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub name: Option<&'static str>,
+    pub build: Build,
+}
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+/// The checked-in input for a day, e.g. `2024/input/2024/day15.txt`.
+pub fn default_input(year: u32, day: u8) -> PathBuf {
+    workspace_root().join(format!("{year}/input/{year}/day{day}.txt"))
+}
+
+/// Reads an input file, normalised as every generator expects: LF line endings, no byte order mark
+/// and no trailing newline.
+pub fn read_input(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path).map(|input| normalize(&input).into_owned())
+}
+
+/// The step-based simulation behind a day's part, for days that have one.
+pub fn simulation(
+    year: u32,
+    day: u8,
+    part: u8,
+    input: &str,
+) -> Option<Result<Box<dyn Simulation>, PuzzleParseError>> {
+    match year {
+        2024 => advent_of_code_2024::simulation(day, part, input),
+        2025 => advent_of_code_2025::simulation(day, part, input),
+        _ => None,
+    }
+}
+
+/// A random input for a day from `seed`, for days that have a generator.
+pub fn generate_input(year: u32, day: u8, size: usize, seed: u64) -> Option<String> {
+    match year {
+        2024 => advent_of_code_2024::generate_input(day, size, seed),
+        2025 => advent_of_code_2025::generate_input(day, size, seed),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovers_every_year() {
+        assert!(
+            SOLUTIONS
+                .iter()
+                .any(|s| (s.year, s.day, s.part) == (2024, 15, 2))
+        );
+        assert!(
+            SOLUTIONS
+                .iter()
+                .any(|s| (s.year, s.day, s.part) == (2025, 12, 1))
+        );
+    }
+
+    #[test]
+    fn test_runs_a_solution() {
+        let solution = SOLUTIONS
+            .iter()
+            .find(|s| (s.year, s.day, s.part) == (2024, 1, 1))
+            .unwrap();
+        let runner =
+            (solution.build)(ArcStr::from("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n")).unwrap();
+        assert_eq!(runner.try_run().unwrap().to_string(), "11");
+    }
+}
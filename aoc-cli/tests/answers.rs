@@ -0,0 +1,63 @@
+//! Runs every registered solution against its checked-in input and compares the result with
+//! `answers.toml`. Days without an input file or a known answer are skipped.
+//!
+//! Each part's time is also checked against `budgets.toml`. Times depend on the machine and
+//! whatever else it's running, so set `AOC_SKIP_BUDGETS` to check only the answers on a busy one.
+
+use std::env;
+
+use aoc_cli::{
+    answers::{self, Answers},
+    budget::{self, Budgets},
+    report::{self, Status},
+    solutions::{self, SOLUTIONS},
+};
+use aoc_runner::ArcStr;
+
+#[test]
+fn test_known_answers() {
+    let answers = Answers::load(&answers::default_path()).unwrap();
+    let budgets = env::var_os("AOC_SKIP_BUDGETS")
+        .is_none()
+        .then(|| Budgets::load(&budget::default_path()).unwrap());
+
+    let mut failures = Vec::new();
+    for solution in SOLUTIONS {
+        let label = format!(
+            "{} day {} part {}",
+            solution.year, solution.day, solution.part
+        );
+
+        let Some(expected) = answers.get(solution.year, solution.day, solution.part) else {
+            eprintln!("{label}: no known answer, skipping");
+            continue;
+        };
+        let path = solutions::default_input(solution.year, solution.day);
+        let Ok(input) = solutions::read_input(&path) else {
+            eprintln!("{label}: no input at {}, skipping", path.display());
+            continue;
+        };
+
+        let report = report::run(solution, &ArcStr::from(&input));
+        let time =
+            report.generator_time.unwrap_or_default() + report.solver_time.unwrap_or_default();
+        if let Some(budget) = budgets
+            .as_ref()
+            .and_then(|budgets| budgets.get(solution.year, solution.day))
+            && time > budget
+        {
+            failures.push(format!(
+                "{label}: took {time:.2?}, over its budget of {budget:?}"
+            ));
+        }
+        match (report.status, report.answer) {
+            (Status::Ok, Some(answer)) if answer.to_string() == expected => {}
+            (Status::Ok, Some(answer)) => {
+                failures.push(format!("{label}: expected {expected}, got {answer}"))
+            }
+            _ => failures.push(format!("{label}: failed\n{}", report.error.unwrap())),
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,178 @@
+//! Fetches and submits against the mock server, so the whole flow runs offline.
+
+use std::{
+    fs,
+    path::PathBuf,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use aoc_cli::{
+    answers::{self, Answers},
+    client::{Client, ClientError, Hint, Verdict},
+    mock::{MockConfig, MockServer},
+    scaffold,
+};
+
+const SESSION: &str = "53616c7465645f5f";
+
+/// A fresh directory to cache inputs in.
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn mock_client(server: &MockServer, cache_dir: PathBuf) -> Client {
+    Client::new(SESSION)
+        .base_url(server.url())
+        .cache_dir(cache_dir)
+        .min_interval(Duration::ZERO)
+}
+
+#[test]
+fn test_fetch_caches_input() {
+    let server = MockConfig::new(SESSION)
+        .input(2024, 1, "3   4\n4   3")
+        .start()
+        .unwrap();
+    let mut client = mock_client(&server, cache_dir("fetch"));
+
+    assert_eq!(client.input(2024, 1).unwrap(), "3   4\n4   3\n");
+    assert_eq!(client.input(2024, 1).unwrap(), "3   4\n4   3\n");
+    assert_eq!(server.requests(), ["GET /2024/day/1/input"]);
+    assert!(client.cached_input_path(2024, 1).exists());
+
+    assert!(matches!(
+        client.input(2024, 2),
+        Err(ClientError::NotFound { year: 2024, day: 2 })
+    ));
+}
+
+#[test]
+fn test_fetch_after_scaffold() {
+    let server = MockConfig::new(SESSION)
+        .input(2024, 1, "3   4\n4   3")
+        .start()
+        .unwrap();
+    let root = cache_dir("scaffold");
+    fs::create_dir_all(root.join("2024/src")).unwrap();
+    fs::write(
+        root.join("2024/Cargo.toml"),
+        "[package]\nname = \"advent-of-code-2024\"\n",
+    )
+    .unwrap();
+    fs::write(root.join("2024/src/lib.rs"), "aoc_lib! { year = 2024 }\n").unwrap();
+    scaffold::new_day(&root, 2024, 1).unwrap();
+    let mut client = mock_client(&server, root);
+
+    // The placeholder `aoc new` leaves isn't mistaken for a fetched input.
+    assert_eq!(client.input(2024, 1).unwrap(), "3   4\n4   3\n");
+    assert_eq!(server.requests(), ["GET /2024/day/1/input"]);
+}
+
+#[test]
+fn test_fetch_needs_session() {
+    let server = MockConfig::new(SESSION)
+        .input(2024, 1, "3   4")
+        .start()
+        .unwrap();
+    let mut client = Client::new("expired")
+        .base_url(server.url())
+        .cache_dir(cache_dir("unauthorized"));
+
+    assert!(matches!(
+        client.input(2024, 1),
+        Err(ClientError::Unauthorized)
+    ));
+    assert!(!client.cached_input_path(2024, 1).exists());
+}
+
+#[test]
+fn test_submit() {
+    let server = MockConfig::new(SESSION)
+        .answer(2024, 1, 1, "11")
+        .answer(2024, 1, 2, "31")
+        .cooldown(Duration::from_secs(90))
+        .start()
+        .unwrap();
+    let mut client = mock_client(&server, cache_dir("submit"));
+
+    assert_eq!(
+        client.submit(2024, 1, 2, "31").unwrap(),
+        Verdict::WrongLevel
+    );
+    assert_eq!(
+        client.submit(2024, 1, 1, "12").unwrap(),
+        Verdict::Incorrect {
+            hint: Some(Hint::TooHigh)
+        }
+    );
+    assert_eq!(
+        client.submit(2024, 1, 1, "11").unwrap(),
+        Verdict::TooSoon {
+            wait: Some(Duration::from_secs(90))
+        }
+    );
+
+    let server = MockConfig::new(SESSION)
+        .answer(2024, 1, 1, "11")
+        .answer(2024, 1, 2, "31")
+        .start()
+        .unwrap();
+    let mut client = mock_client(&server, cache_dir("submit"));
+    assert_eq!(client.submit(2024, 1, 1, "11").unwrap(), Verdict::Correct);
+    assert_eq!(client.submit(2024, 1, 2, "31").unwrap(), Verdict::Correct);
+    assert_eq!(
+        client.submit(2024, 1, 2, "31").unwrap(),
+        Verdict::WrongLevel
+    );
+}
+
+#[test]
+fn test_rate_limit() {
+    let server = MockConfig::new(SESSION)
+        .answer(2024, 1, 1, "11")
+        .cooldown(Duration::ZERO)
+        .start()
+        .unwrap();
+    let mut client =
+        mock_client(&server, cache_dir("rate_limit")).min_interval(Duration::from_millis(200));
+
+    let start = Instant::now();
+    for _ in 0..3 {
+        client.submit(2024, 1, 1, "10").unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(400));
+
+    // A new client, as in the next run of `aoc`, waits for the last request made by the old one.
+    let mut client = Client::new(SESSION)
+        .base_url(server.url())
+        .cache_dir(PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("rate_limit"))
+        .min_interval(Duration::from_millis(200));
+    let start = Instant::now();
+    client.submit(2024, 1, 1, "10").unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
+
+#[test]
+fn test_cli_submits_solution_answer() {
+    let answers = Answers::load(&answers::default_path()).unwrap();
+    let server = MockConfig::new(SESSION)
+        .answer(2024, 1, 1, answers.get(2024, 1, 1).unwrap())
+        .start()
+        .unwrap();
+
+    // The checked-in input is already cached, so only the answer goes to the server.
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["submit", "2024", "1", "--part", "1"])
+        .env("AOC_SESSION", SESSION)
+        .env("AOC_BASE_URL", server.url())
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("that's the right answer"), "{stdout}");
+    assert_eq!(server.requests(), ["POST /2024/day/1/answer"]);
+}
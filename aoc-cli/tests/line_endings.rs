@@ -0,0 +1,471 @@
+//! Runs each day's puzzle example through its solution with CRLF line endings, a byte order mark
+//! and extra trailing newlines, exactly as given to the generator, and checks the answer matches
+//! the one for the example as written.
+
+use aoc_cli::{
+    report::{self, Status},
+    solutions::SOLUTIONS,
+};
+use aoc_core::input::assert_line_ending_tolerant;
+use aoc_runner::ArcStr;
+
+/// An example from each day's puzzle, with the part to run on it.
+const EXAMPLES: &[(u32, u8, u8, &str)] = &[
+    (
+        2024,
+        1,
+        2,
+        r"3   4
+4   3
+2   5
+1   3
+3   9
+3   3",
+    ),
+    (
+        2024,
+        2,
+        2,
+        r"7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9",
+    ),
+    (
+        2024,
+        3,
+        1,
+        r"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
+    ),
+    (
+        2024,
+        3,
+        2,
+        r"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+    ),
+    (
+        2024,
+        4,
+        2,
+        r"MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX",
+    ),
+    (
+        2024,
+        5,
+        2,
+        r"47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47",
+    ),
+    (
+        2024,
+        6,
+        1,
+        r"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...",
+    ),
+    (
+        2024,
+        7,
+        2,
+        r"190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20",
+    ),
+    (
+        2024,
+        8,
+        2,
+        r"............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............",
+    ),
+    (2024, 9, 2, r"2333133121414131402"),
+    (
+        2024,
+        10,
+        1,
+        r"89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732",
+    ),
+    (2024, 11, 1, r"125 17"),
+    (
+        2024,
+        12,
+        1,
+        r"RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE",
+    ),
+    (
+        2024,
+        13,
+        1,
+        r"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+",
+    ),
+    (
+        2024,
+        14,
+        1,
+        r"p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3",
+    ),
+    (
+        2024,
+        15,
+        1,
+        r"##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^",
+    ),
+    (
+        2024,
+        15,
+        2,
+        r"#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^",
+    ),
+    (
+        2025,
+        1,
+        2,
+        r"L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82",
+    ),
+    (
+        2025,
+        2,
+        1,
+        r"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124",
+    ),
+    (
+        2025,
+        3,
+        1,
+        r"987654321111111
+811111111111119
+234234234234278
+818181911112111",
+    ),
+    (
+        2025,
+        4,
+        1,
+        r"..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.",
+    ),
+    (
+        2025,
+        5,
+        1,
+        r"3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32",
+    ),
+    (
+        2025,
+        6,
+        1,
+        r"123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ",
+    ),
+    (
+        2025,
+        6,
+        2,
+        r"123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ",
+    ),
+    (
+        2025,
+        7,
+        1,
+        r".......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............",
+    ),
+    (
+        2025,
+        8,
+        2,
+        r"162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689",
+    ),
+    (
+        2025,
+        9,
+        1,
+        r"7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3",
+    ),
+    (
+        2025,
+        10,
+        1,
+        r"[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
+    ),
+    (
+        2025,
+        11,
+        1,
+        r"aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out",
+    ),
+    (
+        2025,
+        12,
+        1,
+        r"0:
+###
+##.
+##.
+
+1:
+###
+##.
+.##
+
+2:
+.##
+###
+##.
+
+3:
+##.
+###
+##.
+
+4:
+###
+#..
+###
+
+5:
+###
+.#.
+###
+
+4x4: 0 0 0 0 2 0
+12x5: 1 0 1 0 2 2
+12x5: 1 0 1 0 3 2",
+    ),
+];
+
+#[test]
+fn test_examples_are_line_ending_tolerant() {
+    for &(year, day, part, example) in EXAMPLES {
+        for solution in SOLUTIONS
+            .iter()
+            .filter(|s| (s.year, s.day, s.part) == (year, day, part))
+        {
+            assert_line_ending_tolerant(example, |input| {
+                let report = report::run(solution, &ArcStr::from(input));
+                assert_eq!(
+                    report.status,
+                    Status::Ok,
+                    "{}: {:?}",
+                    report.label(),
+                    report.error
+                );
+                report.answer.map(|answer| answer.to_string())
+            });
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! A common type for puzzle answers, whichever integer type a solver happens to return.
+
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use serde::{Serialize, Serializer};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(i128),
+    Text(String),
+}
+
+macro_rules! impl_from {
+    ($variant:ident($inner:ty): $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $inner)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Signed(i64): i8, i16, i32, i64, isize);
+impl_from!(Unsigned(u64): u8, u16, u32, u64, usize);
+impl_from!(Big(i128): i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Recovers an answer from a solver's printed output. Integers are stored in the smallest variant
+/// that holds them, unless that would change how they print (e.g. `007`), so parsing never loses
+/// information.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let answer = if let Ok(n) = s.parse::<u64>() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse::<i64>() {
+            Answer::Signed(n)
+        } else if let Ok(n) = s.parse::<i128>() {
+            Answer::Big(n)
+        } else {
+            return Ok(Answer::Text(s.to_string()));
+        };
+
+        Ok(if answer.to_string() == s {
+            answer
+        } else {
+            Answer::Text(s.to_string())
+        })
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Big(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Integers are written as JSON numbers and text as strings.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Signed(n) => serializer.serialize_i64(*n),
+            Answer::Unsigned(n) => serializer.serialize_u64(*n),
+            Answer::Big(n) => serializer.serialize_i128(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(-3i32), Answer::Signed(-3));
+        assert_eq!(Answer::from(7usize), Answer::Unsigned(7));
+        assert_eq!(Answer::from(1i128 << 100), Answer::Big(1 << 100));
+        assert_eq!(Answer::from("ABC"), Answer::Text("ABC".to_string()));
+    }
+
+    #[test]
+    fn test_parse_round_trips() {
+        for (s, answer) in [
+            ("1318523", Answer::Unsigned(1318523)),
+            ("-42", Answer::Signed(-42)),
+            ("18446744073709551616", Answer::Big(1 << 64)),
+            ("007", Answer::Text("007".to_string())),
+            ("+5", Answer::Text("+5".to_string())),
+            ("6,0,4", Answer::Text("6,0,4".to_string())),
+        ] {
+            let parsed: Answer = s.parse().unwrap();
+            assert_eq!(parsed, answer);
+            assert_eq!(parsed.to_string(), s);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! nom parsers for the formats puzzle inputs keep coming back to: one record per line, numbers
+//! separated by commas or spaces, `x,y` coordinates, `a-b` ranges, sections separated by a blank
+//! line, and character maps.
+//!
+//! They accept LF and CRLF line endings alike, and are meant to be run with
+//! [`parse_puzzle`](crate::parse::parse_puzzle), which trims what else differs between copies of
+//! an input.
+
+use std::ops::RangeInclusive;
+
+use glam::{I64Vec2, I64Vec3, IVec2};
+use nom::{
+    IResult, Parser,
+    character::complete::{char, i32, i64, line_ending, none_of, space1},
+    combinator::map_opt,
+    error::Error,
+    multi::{many1, separated_list1},
+    sequence::{pair, preceded, separated_pair},
+};
+
+/// One or more records, one per line.
+pub fn lines<'a, P>(
+    line: P,
+) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<&'a str>>
+where
+    P: Parser<&'a str, Error = Error<&'a str>>,
+{
+    separated_list1(line_ending, line)
+}
+
+/// One or more items separated by commas, e.g. `75,47,61`.
+pub fn comma_separated<'a, P>(
+    item: P,
+) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<&'a str>>
+where
+    P: Parser<&'a str, Error = Error<&'a str>>,
+{
+    separated_list1(char(','), item)
+}
+
+/// One or more items separated by runs of spaces or tabs, e.g. `7 6 4 2 1`.
+pub fn space_separated<'a, P>(
+    item: P,
+) -> impl Parser<&'a str, Output = Vec<P::Output>, Error = Error<&'a str>>
+where
+    P: Parser<&'a str, Error = Error<&'a str>>,
+{
+    separated_list1(space1, item)
+}
+
+/// A signed `x,y` pair.
+pub fn ivec2(input: &str) -> IResult<&str, IVec2> {
+    separated_pair(i32, char(','), i32)
+        .map(|(x, y)| IVec2::new(x, y))
+        .parse(input)
+}
+
+/// A signed `x,y` pair.
+pub fn i64vec2(input: &str) -> IResult<&str, I64Vec2> {
+    separated_pair(i64, char(','), i64)
+        .map(|(x, y)| I64Vec2::new(x, y))
+        .parse(input)
+}
+
+/// A signed `x,y,z` triple.
+pub fn i64vec3(input: &str) -> IResult<&str, I64Vec3> {
+    (i64, preceded(char(','), i64), preceded(char(','), i64))
+        .map(|(x, y, z)| I64Vec3::new(x, y, z))
+        .parse(input)
+}
+
+/// An inclusive range written `start-end`, with both ends parsed by `bound`.
+pub fn inclusive_range<'a, P>(
+    bound: P,
+) -> impl Parser<&'a str, Output = RangeInclusive<P::Output>, Error = Error<&'a str>>
+where
+    P: Parser<&'a str, Error = Error<&'a str>> + Clone,
+{
+    separated_pair(bound.clone(), char('-'), bound).map(|(start, end)| start..=end)
+}
+
+/// Two sections of different formats, separated by a blank line.
+pub fn sections<'a, A, B>(
+    first: A,
+    second: B,
+) -> impl Parser<&'a str, Output = (A::Output, B::Output), Error = Error<&'a str>>
+where
+    A: Parser<&'a str, Error = Error<&'a str>>,
+    B: Parser<&'a str, Error = Error<&'a str>>,
+{
+    separated_pair(first, pair(line_ending, line_ending), second)
+}
+
+/// The rows of a character map, converting each character with `cell`. Rows may differ in
+/// length; pass them to [`Grid::from_rows`](crate::grid::Grid::from_rows) to check they don't.
+pub fn char_rows<'a, T>(
+    cell: impl Fn(char) -> Option<T>,
+) -> impl Parser<&'a str, Output = Vec<Vec<T>>, Error = Error<&'a str>> {
+    move |input: &'a str| {
+        separated_list1(line_ending, many1(map_opt(none_of("\r\n"), &cell))).parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::character::complete::u64;
+
+    use super::*;
+    use crate::parse::parse_puzzle;
+
+    #[test]
+    fn test_lists_and_vectors() {
+        assert_eq!(
+            parse_puzzle(0, "1,2\n3,4", lines(comma_separated(u64))),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+        assert_eq!(
+            parse_puzzle(0, "7  6\t4", space_separated(u64)),
+            Ok(vec![7, 6, 4])
+        );
+        assert_eq!(parse_puzzle(0, "-3,4", ivec2), Ok(IVec2::new(-3, 4)));
+        assert_eq!(
+            parse_puzzle(0, "9000000000,-1", i64vec2),
+            Ok(I64Vec2::new(9_000_000_000, -1))
+        );
+        assert_eq!(
+            parse_puzzle(0, "1,-2,3", i64vec3),
+            Ok(I64Vec3::new(1, -2, 3))
+        );
+        assert!(parse_puzzle(0, "1,2", i64vec3).is_err());
+    }
+
+    #[test]
+    fn test_ranges_and_sections() {
+        assert_eq!(
+            parse_puzzle(
+                0,
+                "3-5\n10-14\n\n1\n5",
+                sections(lines(inclusive_range(u64)), lines(u64))
+            ),
+            Ok((vec![3..=5, 10..=14], vec![1, 5]))
+        );
+    }
+
+    #[test]
+    fn test_char_rows() {
+        let wall = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        assert_eq!(
+            parse_puzzle(0, "#.\n.#", char_rows(wall)),
+            Ok(vec![vec![true, false], vec![false, true]])
+        );
+        assert_eq!(
+            parse_puzzle(0, "#.\n\n<>", sections(char_rows(wall), char_rows(Some))),
+            Ok((vec![vec![true, false]], vec![vec!['<', '>']]))
+        );
+        assert!(parse_puzzle(0, "#x", char_rows(wall)).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+//! Diagnostics that solvers can emit without cluttering normal runs, benchmarks or tests.
+//!
+//! Nothing is printed unless the runner raises the level: [`debug!`](crate::debug) is for
+//! intermediate values and [`trace!`](crate::trace) for anything bulky, like a render of the whole
+//! map. Messages go to stderr, so they never mix with answers or JSON reports on stdout.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Debug,
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Off,
+        1 => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+/// Whether messages at `level` are currently printed.
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+/// Prints an intermediate value when running with `-v`. The arguments are only evaluated if it
+/// will be printed.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::diag::enabled($crate::diag::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Prints a bulky diagnostic, such as a render of the map, when running with `--trace`. The
+/// arguments are only evaluated if it will be printed.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::diag::enabled($crate::diag::Level::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(level(), Level::Off);
+        assert!(!enabled(Level::Debug));
+
+        set_level(Level::Debug);
+        assert!(enabled(Level::Debug));
+        assert!(!enabled(Level::Trace));
+
+        set_level(Level::Trace);
+        assert!(enabled(Level::Debug) && enabled(Level::Trace));
+        assert!(!enabled(Level::Off));
+
+        set_level(Level::Off);
+    }
+}
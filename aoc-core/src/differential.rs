@@ -0,0 +1,71 @@
+//! Differential testing of optimised solvers against simple reference implementations.
+//!
+//! Days register a pair by calling [`assert_agree`] from a test with a proptest strategy for
+//! small random inputs. Set `PROPTEST_CASES` to run more or fewer cases than the default 256.
+
+use std::fmt::Debug;
+
+use proptest::{
+    prop_assert_eq,
+    strategy::Strategy,
+    test_runner::{Config, TestError, TestRunner},
+};
+
+/// Runs `reference` and `optimised` on inputs drawn from `strategy`, panicking with the smallest
+/// input they disagree on (or either of them panics on) that proptest can shrink to.
+pub fn assert_agree<S, R>(
+    strategy: S,
+    reference: impl Fn(&S::Value) -> R,
+    optimised: impl Fn(&S::Value) -> R,
+) where
+    S: Strategy,
+    S::Value: Debug,
+    R: PartialEq + Debug,
+{
+    let mut runner = TestRunner::new(Config::default());
+    let result = runner.run(&strategy, |input| {
+        prop_assert_eq!(
+            reference(&input),
+            optimised(&input),
+            "reference vs optimised"
+        );
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => {}
+        Err(TestError::Fail(reason, input)) => {
+            panic!("reference and optimised disagree on {input:#?}\n{reason}")
+        }
+        Err(TestError::Abort(reason)) => panic!("differential test aborted: {reason}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_agreeing_pair_passes() {
+        assert_agree(
+            vec(0..100u32, 0..10),
+            |values| values.iter().sum::<u32>(),
+            |values| values.iter().rev().sum(),
+        );
+    }
+
+    #[test]
+    fn test_disagreement_is_shrunk() {
+        let panic = std::panic::catch_unwind(|| {
+            assert_agree(0..1000u32, |&n| n.min(100), |&n| n);
+        })
+        .unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(
+            message.starts_with("reference and optimised disagree on 101\n"),
+            "{message}"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign},
+};
+
+use glam::IVec2;
+
+/// One of the four orthogonal compass directions, with north pointing up the screen (`-y`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Dir4 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Dir4; 4] = [Dir4::N, Dir4::E, Dir4::S, Dir4::W];
+
+    pub fn iter() -> impl Iterator<Item = Dir4> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Dir4::E | Dir4::W)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir4::N | Dir4::S)
+    }
+
+    /// Parses `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Dir4::N),
+            '>' => Some(Dir4::E),
+            'v' => Some(Dir4::S),
+            '<' => Some(Dir4::W),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::N => '^',
+            Dir4::E => '>',
+            Dir4::S => 'v',
+            Dir4::W => '<',
+        }
+    }
+
+    /// Parses a compass letter (`N`, `E`, `S`, `W`) or a relative one (`U`, `R`, `D`, `L`).
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'N' | 'U' => Some(Dir4::N),
+            'E' | 'R' => Some(Dir4::E),
+            'S' | 'D' => Some(Dir4::S),
+            'W' | 'L' => Some(Dir4::W),
+            _ => None,
+        }
+    }
+
+    pub fn letter(self) -> char {
+        match self {
+            Dir4::N => 'N',
+            Dir4::E => 'E',
+            Dir4::S => 'S',
+            Dir4::W => 'W',
+        }
+    }
+}
+
+impl From<Dir4> for IVec2 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::N => IVec2::NEG_Y,
+            Dir4::E => IVec2::X,
+            Dir4::S => IVec2::Y,
+            Dir4::W => IVec2::NEG_X,
+        }
+    }
+}
+
+impl TryFrom<IVec2> for Dir4 {
+    type Error = IVec2;
+
+    fn try_from(v: IVec2) -> Result<Self, Self::Error> {
+        Self::iter().find(|&dir| IVec2::from(dir) == v).ok_or(v)
+    }
+}
+
+impl Add<Dir4> for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, dir: Dir4) -> IVec2 {
+        self + IVec2::from(dir)
+    }
+}
+
+impl AddAssign<Dir4> for IVec2 {
+    fn add_assign(&mut self, dir: Dir4) {
+        *self += IVec2::from(dir);
+    }
+}
+
+/// Parses an arrow glyph, as used in puzzle maps.
+impl TryFrom<char> for Dir4 {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_arrow(c).ok_or(c)
+    }
+}
+
+/// Prints the arrow glyph.
+impl fmt::Display for Dir4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+/// One of the eight compass directions, including diagonals, with north pointing up the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Dir8> {
+        Self::ALL.into_iter()
+    }
+
+    /// Turns 45 degrees anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Parses an arrow such as `↑` or `↘`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        Self::iter().find(|dir| dir.arrow() == c)
+    }
+
+    pub fn arrow(self) -> char {
+        ['↑', '↗', '→', '↘', '↓', '↙', '←', '↖'][self as usize]
+    }
+
+    /// Parses a compass point such as `N` or `SW`.
+    pub fn from_letters(s: &str) -> Option<Self> {
+        Self::iter().find(|dir| dir.letters() == s)
+    }
+
+    pub fn letters(self) -> &'static str {
+        ["N", "NE", "E", "SE", "S", "SW", "W", "NW"][self as usize]
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[dir as usize * 2]
+    }
+}
+
+impl From<Dir8> for IVec2 {
+    fn from(dir: Dir8) -> Self {
+        match dir {
+            Dir8::N => IVec2::new(0, -1),
+            Dir8::NE => IVec2::new(1, -1),
+            Dir8::E => IVec2::new(1, 0),
+            Dir8::SE => IVec2::new(1, 1),
+            Dir8::S => IVec2::new(0, 1),
+            Dir8::SW => IVec2::new(-1, 1),
+            Dir8::W => IVec2::new(-1, 0),
+            Dir8::NW => IVec2::new(-1, -1),
+        }
+    }
+}
+
+impl TryFrom<IVec2> for Dir8 {
+    type Error = IVec2;
+
+    fn try_from(v: IVec2) -> Result<Self, Self::Error> {
+        Self::iter().find(|&dir| IVec2::from(dir) == v).ok_or(v)
+    }
+}
+
+impl Add<Dir8> for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, dir: Dir8) -> IVec2 {
+        self + IVec2::from(dir)
+    }
+}
+
+impl AddAssign<Dir8> for IVec2 {
+    fn add_assign(&mut self, dir: Dir8) {
+        *self += IVec2::from(dir);
+    }
+}
+
+/// Prints the compass point, e.g. `NE`.
+impl fmt::Display for Dir8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.letters())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir4_turns() {
+        assert_eq!(Dir4::N.turn_right(), Dir4::E);
+        assert_eq!(Dir4::N.turn_left(), Dir4::W);
+        assert_eq!(Dir4::W.turn_right(), Dir4::N);
+        assert_eq!(Dir4::E.reverse(), Dir4::W);
+        assert_eq!(IVec2::ONE + Dir4::N, IVec2::X);
+        for dir in Dir4::iter() {
+            assert_eq!(IVec2::from(dir.turn_right()), IVec2::from(dir).perp());
+            assert_eq!(Dir4::try_from(IVec2::from(dir)), Ok(dir));
+        }
+    }
+
+    #[test]
+    fn test_dir4_glyphs() {
+        let arrows = "^>v<".chars().map(|c| Dir4::try_from(c).unwrap());
+        assert!(arrows.eq(Dir4::iter()));
+        assert_eq!(Dir4::from_letter('L'), Some(Dir4::W));
+        assert_eq!(Dir4::try_from('x'), Err('x'));
+        assert_eq!(Dir4::S.to_string(), "v");
+    }
+
+    #[test]
+    fn test_dir8() {
+        assert_eq!(Dir8::NW.turn_right(), Dir8::N);
+        assert_eq!(Dir8::N.turn_left(), Dir8::NW);
+        assert_eq!(Dir8::NE.reverse(), Dir8::SW);
+        assert_eq!(Dir8::from(Dir4::W), Dir8::W);
+        assert_eq!(Dir8::from_letters("SE"), Some(Dir8::SE));
+        assert_eq!(Dir8::from_arrow('↙'), Some(Dir8::SW));
+        for dir in Dir8::iter() {
+            assert_eq!(Dir8::try_from(IVec2::from(dir)), Ok(dir));
+            assert_eq!(dir.is_diagonal(), IVec2::from(dir).element_product() != 0);
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Planar geometry on integer points: axis-aligned rectangles, segments, simple polygons, and the
+//! lattice points along lines. Everything is exact; cross products are taken in `i128` so that
+//! coordinates anywhere in `i64` range can't overflow them.
+
+use std::iter;
+
+use glam::I64Vec2;
+
+use crate::math::gcd;
+
+/// The z component of `a × b`: positive when `b` is anticlockwise of `a` with `y` up, or
+/// clockwise of it on screen, where `y` points down.
+pub fn cross(a: I64Vec2, b: I64Vec2) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+/// The smallest step between lattice points on the line from the origin through `d`, e.g.
+/// `(2, 1)` for `(6, 3)`. Zero stays zero.
+pub fn lattice_step(d: I64Vec2) -> I64Vec2 {
+    match gcd(d.x, d.y) {
+        0 => d,
+        g => d / g,
+    }
+}
+
+/// The points `from`, `towards`, and on in steps of the distance between them, skipping any
+/// lattice points in between. The ray never ends, so bound it with `take_while`.
+///
+/// # Panics
+///
+/// If the points are equal, as they don't give a direction.
+pub fn ray(from: I64Vec2, towards: I64Vec2) -> impl Iterator<Item = I64Vec2> {
+    assert_ne!(from, towards, "a ray needs two distinct points");
+    let step = towards - from;
+    iter::successors(Some(from), move |&p| Some(p + step))
+}
+
+/// The tiles from `min` to `max` inclusive, e.g. the rectangle with two given tiles as opposite
+/// corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: I64Vec2,
+    pub max: I64Vec2,
+}
+
+impl Rect {
+    /// The rectangle with `a` and `b` as opposite corners, in either order.
+    pub fn spanning(a: I64Vec2, b: I64Vec2) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    /// How many tiles it covers.
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: I64Vec2) -> bool {
+        p.cmpge(self.min).all() && p.cmple(self.max).all()
+    }
+
+    /// The tiles in both, if there are any.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        min.cmple(max).all().then_some(Rect { min, max })
+    }
+
+    /// Whether any part of `other` lies strictly inside this rectangle's outline, treating both
+    /// as continuous regions between their corner points. Touching the outline doesn't count,
+    /// and `other` may be a segment's [bounds](Segment::bounds).
+    pub fn crosses_interior(&self, other: &Rect) -> bool {
+        other.max.cmpgt(self.min).all() && other.min.cmplt(self.max).all()
+    }
+}
+
+/// The closed line segment between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub a: I64Vec2,
+    pub b: I64Vec2,
+}
+
+impl Segment {
+    pub fn new(a: I64Vec2, b: I64Vec2) -> Self {
+        Self { a, b }
+    }
+
+    /// The smallest rectangle containing the segment.
+    pub fn bounds(&self) -> Rect {
+        Rect::spanning(self.a, self.b)
+    }
+
+    pub fn contains(&self, p: I64Vec2) -> bool {
+        cross(self.b - self.a, p - self.a) == 0 && self.bounds().contains(p)
+    }
+
+    /// Whether the segments share at least one point, including touching at an end or
+    /// overlapping along a common line.
+    pub fn intersects(&self, other: &Segment) -> bool {
+        let side = |s: &Segment, p: I64Vec2| cross(s.b - s.a, p - s.a).signum();
+        let (d1, d2) = (side(self, other.a), side(self, other.b));
+        let (d3, d4) = (side(other, self.a), side(other, self.b));
+        if d1 * d2 < 0 && d3 * d4 < 0 {
+            return true;
+        }
+        self.contains(other.a)
+            || self.contains(other.b)
+            || other.contains(self.a)
+            || other.contains(self.b)
+    }
+
+    /// The lattice points on the segment, from `a` to `b`.
+    pub fn lattice_points(&self) -> impl Iterator<Item = I64Vec2> {
+        let d = self.b - self.a;
+        let step = lattice_step(d);
+        let count = gcd(d.x, d.y) + 1;
+        (0..count).map(move |i| self.a + step * i)
+    }
+}
+
+/// Where a point is relative to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// A simple polygon given by its vertices in order around it, either way round. The last vertex
+/// joins back to the first.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    pub vertices: Vec<I64Vec2>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<I64Vec2>) -> Self {
+        Self { vertices }
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices
+            .iter()
+            .zip(next)
+            .map(|(&a, &b)| Segment::new(a, b))
+    }
+
+    /// Twice the enclosed area, by the shoelace formula, which keeps it an integer.
+    pub fn double_area(&self) -> i128 {
+        self.edges().map(|e| cross(e.a, e.b)).sum::<i128>().abs()
+    }
+
+    /// How many lattice points lie on the edges.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|e| gcd(e.b.x - e.a.x, e.b.y - e.a.y))
+            .sum()
+    }
+
+    /// How many lattice points lie strictly inside, by Pick's theorem.
+    pub fn interior_points(&self) -> i128 {
+        (self.double_area() - self.boundary_points() as i128 + 2) / 2
+    }
+
+    /// Whether `p` is inside, on an edge, or outside, by counting the edges crossed by a ray
+    /// from it towards `+x`.
+    pub fn locate(&self, p: I64Vec2) -> Location {
+        let mut inside = false;
+        for edge in self.edges() {
+            if edge.contains(p) {
+                return Location::Boundary;
+            }
+            let (a, b) = (edge.a, edge.b);
+            if (a.y > p.y) != (b.y > p.y) && (cross(b - a, p - a) > 0) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+        if inside {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: i64, y: i64) -> I64Vec2 {
+        I64Vec2::new(x, y)
+    }
+
+    #[test]
+    fn test_lattice() {
+        assert_eq!(lattice_step(v(6, -3)), v(2, -1));
+        assert_eq!(lattice_step(v(0, -4)), v(0, -1));
+        assert_eq!(lattice_step(v(0, 0)), v(0, 0));
+        assert_eq!(
+            ray(v(1, 1), v(5, 3)).take(3).collect::<Vec<_>>(),
+            [v(1, 1), v(5, 3), v(9, 5)]
+        );
+        assert_eq!(
+            Segment::new(v(4, 0), v(0, 6))
+                .lattice_points()
+                .collect::<Vec<_>>(),
+            [v(4, 0), v(2, 3), v(0, 6)]
+        );
+        assert_eq!(Segment::new(v(2, 2), v(2, 2)).lattice_points().count(), 1);
+    }
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::spanning(v(9, 5), v(2, 3));
+        assert_eq!((rect.min, rect.max), (v(2, 3), v(9, 5)));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (8, 3, 24));
+        assert!(rect.contains(v(2, 5)) && !rect.contains(v(10, 4)));
+        assert_eq!(
+            rect.intersection(&Rect::spanning(v(8, 0), v(20, 3))),
+            Some(Rect::spanning(v(8, 3), v(9, 3)))
+        );
+        assert_eq!(rect.intersection(&Rect::spanning(v(0, 0), v(1, 9))), None);
+
+        // A segment along the outline doesn't cross in; one through the middle does.
+        assert!(!rect.crosses_interior(&Segment::new(v(2, 3), v(9, 3)).bounds()));
+        assert!(!rect.crosses_interior(&Segment::new(v(9, 0), v(9, 9)).bounds()));
+        assert!(rect.crosses_interior(&Segment::new(v(5, 0), v(5, 4)).bounds()));
+    }
+
+    #[test]
+    fn test_segment_intersection() {
+        let s = Segment::new(v(0, 0), v(4, 4));
+        assert!(s.intersects(&Segment::new(v(0, 4), v(4, 0))));
+        assert!(s.intersects(&Segment::new(v(4, 4), v(9, 0))));
+        assert!(s.intersects(&Segment::new(v(2, 2), v(6, 6))));
+        assert!(!s.intersects(&Segment::new(v(5, 5), v(6, 6))));
+        assert!(!s.intersects(&Segment::new(v(1, 0), v(5, 4))));
+        assert!(s.contains(v(3, 3)) && !s.contains(v(3, 2)));
+    }
+
+    #[test]
+    fn test_polygon() {
+        // The loop of red tiles from 2025 day 9's example.
+        let polygon = Polygon::new(vec![
+            v(7, 1),
+            v(11, 1),
+            v(11, 7),
+            v(9, 7),
+            v(9, 5),
+            v(2, 5),
+            v(2, 3),
+            v(7, 3),
+        ]);
+        assert_eq!(polygon.double_area(), 60);
+        assert_eq!(polygon.boundary_points(), 30);
+        assert_eq!(polygon.interior_points(), 16);
+        assert_eq!(polygon.locate(v(8, 4)), Location::Inside);
+        assert_eq!(polygon.locate(v(11, 4)), Location::Boundary);
+        assert_eq!(polygon.locate(v(4, 2)), Location::Outside);
+        assert_eq!(polygon.locate(v(12, 1)), Location::Outside);
+        let inside = (0..14)
+            .flat_map(|x| (0..9).map(move |y| v(x, y)))
+            .filter(|&p| polygon.locate(p) == Location::Inside)
+            .count();
+        assert_eq!(inside as i128, polygon.interior_points());
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use glam::IVec2;
+
+use crate::{
+    dir::{Dir4, Dir8},
+    input::trim,
+    parse::PuzzleParseError,
+};
+
+/// A rectangular grid of cells stored row by row, indexed by `IVec2` with `x` as the column and
+/// `y` as the row, so `(0, 0)` is the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a `width` by `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows, or returns `None` if they are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map, one row per line, converting each character with `T::try_from`.
+    pub fn parse(day: u8, input: &str) -> Result<Self, PuzzleParseError>
+    where
+        T: TryFrom<char>,
+    {
+        Self::parse_with(day, input, |c| T::try_from(c).ok())
+    }
+
+    /// Parses a character map, one row per line, converting each character with `cell`. The
+    /// input is [trimmed](trim) like [`parse_puzzle`](crate::parse::parse_puzzle)'s.
+    pub fn parse_with(
+        day: u8,
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, PuzzleParseError> {
+        let input = trim(input);
+        let rows = input
+            .lines()
+            .map(|line| {
+                line.char_indices()
+                    .map(|(i, c)| {
+                        cell(c).ok_or_else(|| {
+                            PuzzleParseError::at_span(day, input, &line[i..], "a map character")
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_rows(rows).ok_or_else(|| PuzzleParseError::ragged(day, input))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index_of(&self, pos: IVec2) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> + use<T> {
+        let width = self.width;
+        (0..self.width * self.height)
+            .map(move |i| IVec2::new((i % width) as i32, (i / width) as i32))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The in-bounds orthogonal neighbours of `pos`, clockwise from north.
+    pub fn neighbors4(&self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.neighbors(pos, Dir4::iter().map(IVec2::from))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbours of `pos`, clockwise from north.
+    pub fn neighbors8(&self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.neighbors(pos, Dir8::iter().map(IVec2::from))
+    }
+
+    fn neighbors(
+        &self,
+        pos: IVec2,
+        offsets: impl Iterator<Item = IVec2>,
+    ) -> impl Iterator<Item = (IVec2, &T)> {
+        offsets.filter_map(move |offset| {
+            let neighbor = pos + offset;
+            self.get(neighbor).map(|cell| (neighbor, cell))
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The position of the first cell, in row-major order, that matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: IVec2) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos} is outside the {width}x{height} grid"))
+    }
+}
+
+/// Prints one line per row, without a trailing newline.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<char> = Grid::parse(1, "ab\ncd\nef\n").unwrap();
+        assert_eq!(grid.size(), IVec2::new(2, 3));
+        assert_eq!(grid[IVec2::new(1, 2)], 'f');
+        assert_eq!(grid.get(IVec2::new(2, 0)), None);
+        assert_eq!(grid.get(IVec2::new(0, -1)), None);
+        assert_eq!(grid.find(|&c| c == 'd'), Some(IVec2::new(1, 1)));
+        assert_eq!(grid.to_string(), "ab\ncd\nef");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::parse_with(4, "..\n.x", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = Grid::<char>::parse(4, "...\n..").unwrap_err();
+        assert_eq!(error.expected, "a row 3 wide");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let corner = grid
+            .neighbors4(IVec2::ZERO)
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        assert_eq!(corner, vec![IVec2::X, IVec2::Y]);
+        assert_eq!(grid.neighbors8(IVec2::ZERO).count(), 3);
+        assert_eq!(grid.neighbors8(IVec2::ONE).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        grid[IVec2::new(2, 1)] = 7;
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 7]]
+        );
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.columns().count(), 3);
+        assert!(Grid::from_rows(vec![vec![1], vec![]]).is_none());
+    }
+}
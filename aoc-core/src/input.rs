@@ -0,0 +1,157 @@
+//! Normalises raw puzzle inputs before they reach a generator, so that inputs saved with CRLF
+//! line endings, a byte order mark or extra blank lines at the end parse the same as the original.
+
+use std::{borrow::Cow, fmt::Debug};
+
+/// What to do with newlines and blank lines at the end of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingNewlines {
+    /// Removes them all, so the input ends with its last non-blank line.
+    #[default]
+    Strip,
+    /// Strips them, then ends the input with exactly one newline.
+    Single,
+    /// Leaves them as they are.
+    Keep,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Normalize {
+    trailing_newlines: TrailingNewlines,
+}
+
+impl Normalize {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trailing_newlines(mut self, trailing_newlines: TrailingNewlines) -> Self {
+        self.trailing_newlines = trailing_newlines;
+        self
+    }
+
+    /// Strips a leading byte order mark, turns CRLF and lone CR line endings into LF, and handles
+    /// trailing newlines as configured. Only allocates if something needs to change.
+    pub fn apply<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let mut input = Cow::Borrowed(input.strip_prefix('\u{feff}').unwrap_or(input));
+
+        if input.contains('\r') {
+            input = Cow::Owned(input.replace("\r\n", "\n").replace('\r', "\n"));
+        }
+
+        if self.trailing_newlines == TrailingNewlines::Keep {
+            return input;
+        }
+
+        // Cut after the last line with anything but whitespace on it, keeping any spaces at the
+        // end of that line, which can be part of a grid.
+        let content_end = input.trim_end().len();
+        let end = if content_end == 0 {
+            0
+        } else {
+            input[content_end..]
+                .find('\n')
+                .map_or(input.len(), |i| content_end + i)
+        };
+
+        match (self.trailing_newlines, input) {
+            (TrailingNewlines::Single, input) if end > 0 => {
+                Cow::Owned(format!("{}\n", &input[..end]))
+            }
+            (_, Cow::Borrowed(input)) => Cow::Borrowed(&input[..end]),
+            (_, Cow::Owned(mut input)) => {
+                input.truncate(end);
+                Cow::Owned(input)
+            }
+        }
+    }
+}
+
+/// Normalises `input` with the default settings, which strip trailing newlines.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    Normalize::new().apply(input)
+}
+
+/// Strips a leading byte order mark and any blank lines at the end, like [`normalize`], but
+/// without allocating, so line endings are left as they are for the parser to accept either way.
+pub fn trim(input: &str) -> &str {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let content_end = input.trim_end().len();
+    if content_end == 0 {
+        return "";
+    }
+    let end = input[content_end..]
+        .find(['\r', '\n'])
+        .map_or(input.len(), |i| content_end + i);
+    &input[..end]
+}
+
+/// Ways the same puzzle input turns up on disk: with CRLF line endings, a byte order mark, and
+/// one or more trailing newlines.
+pub fn line_ending_variants(input: &str) -> Vec<String> {
+    let crlf = input.replace('\n', "\r\n");
+    vec![
+        format!("{input}\n"),
+        format!("{input}\n\n"),
+        crlf.clone(),
+        format!("{crlf}\r\n"),
+        format!("{crlf}\r\n\r\n"),
+        format!("\u{feff}{input}\n"),
+        format!("\u{feff}{crlf}\r\n"),
+    ]
+}
+
+/// Asserts that `solve` gives the same result for every [line ending variant](line_ending_variants)
+/// of `example`, passed as it is, as it does for `example` itself.
+pub fn assert_line_ending_tolerant<T: PartialEq + Debug>(example: &str, solve: impl Fn(&str) -> T) {
+    let expected = solve(example);
+    for variant in line_ending_variants(example) {
+        assert_eq!(
+            solve(&variant),
+            expected,
+            "different result for {variant:?}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb");
+        assert_eq!(normalize("\u{feff}a\nb\n"), "a\nb");
+        assert_eq!(normalize("a\rb"), "a\nb");
+        assert_eq!(normalize("a  \n \n\t\n"), "a  ");
+        assert_eq!(normalize("\n\n"), "");
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed("a\nb")));
+    }
+
+    #[test]
+    fn test_trailing_newlines() {
+        let single = Normalize::new().trailing_newlines(TrailingNewlines::Single);
+        assert_eq!(single.apply("a\r\n\r\n"), "a\n");
+        assert_eq!(single.apply("a"), "a\n");
+        assert_eq!(single.apply("\n"), "");
+
+        let keep = Normalize::new().trailing_newlines(TrailingNewlines::Keep);
+        assert_eq!(keep.apply("a\r\n\r\n"), "a\n\n");
+    }
+
+    #[test]
+    fn test_trim() {
+        assert_eq!(trim("\u{feff}a\r\nb\r\n\r\n"), "a\r\nb");
+        assert_eq!(trim("a  \n \n\t\n"), "a  ");
+        assert_eq!(trim("\r\n"), "");
+        assert_eq!(trim("a\nb"), "a\nb");
+    }
+
+    #[test]
+    fn test_variants_normalize_to_the_original() {
+        for variant in line_ending_variants("1 2\n3 4") {
+            assert_eq!(normalize(&variant), "1 2\n3 4");
+            assert_eq!(trim(&variant).replace("\r\n", "\n"), "1 2\n3 4");
+        }
+    }
+}
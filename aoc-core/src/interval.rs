@@ -0,0 +1,260 @@
+//! Sets of integers stored as sorted, disjoint inclusive ranges, for puzzles whose ranges are far
+//! too long to walk one element at a time.
+
+use std::{
+    fmt::{self, Debug},
+    iter,
+    ops::RangeInclusive,
+    slice,
+};
+
+/// The integer types an [`IntervalSet`] can hold.
+pub trait Discrete: Copy + Ord + Debug {
+    /// The next integer, or `None` at the type's maximum.
+    fn succ(self) -> Option<Self>;
+    /// The previous integer, or `None` at the type's minimum.
+    fn pred(self) -> Option<Self>;
+    /// Widened so that the length of any range fits.
+    fn to_i128(self) -> i128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(impl Discrete for $t {
+            fn succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+        })*
+    };
+}
+
+impl_discrete!(i32, i64, u32, u64, usize);
+
+/// Whether every integer up to `end` is below `start`, with at least one integer between them.
+fn before<T: Discrete>(end: T, start: T) -> bool {
+    end.succ().is_some_and(|next| next < start)
+}
+
+/// A set of integers, kept as the fewest disjoint ranges: overlapping and adjacent ranges are
+/// coalesced as they're inserted.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Sorted, and each range ends at least two below the start of the next.
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Adds every integer in `range`. An empty range changes nothing.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| before(*r.end(), start));
+        let last = self.ranges.partition_point(|r| !before(end, *r.start()));
+        if first < last {
+            start = start.min(*self.ranges[first].start());
+            end = end.max(*self.ranges[last - 1].end());
+        }
+        self.ranges.splice(first..last, iter::once(start..=end));
+    }
+
+    /// Whether `value` is in the set, by binary search.
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| *r.end() < value);
+        self.ranges.get(i).is_some_and(|r| *r.start() <= value)
+    }
+
+    /// How many integers are in the set.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|r| (r.end().to_i128() - r.start().to_i128()) as u128 + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The disjoint ranges making up the set, in ascending order.
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, RangeInclusive<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter().cloned());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let start = *x.start().max(y.start());
+            let end = *x.end().min(y.end());
+            if start <= end {
+                ranges.push(start..=end);
+            }
+            // Whichever ends first can't overlap anything further along the other.
+            if x.end() < y.end() {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// The integers in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.iter().peekable();
+        for range in self {
+            let (mut start, end) = (*range.start(), *range.end());
+            while others.next_if(|cut| *cut.end() < start).is_some() {}
+            let mut remaining = true;
+            while let Some(cut) = others.peek().filter(|cut| *cut.start() <= end) {
+                if let Some(before) = cut.start().pred().filter(|&before| start <= before) {
+                    ranges.push(start..=before);
+                }
+                match cut.end().succ().filter(|&after| after <= end) {
+                    Some(after) => {
+                        start = after;
+                        others.next();
+                    }
+                    // The cut runs past this range, so may also cut the next one.
+                    None => {
+                        remaining = false;
+                        break;
+                    }
+                }
+            }
+            if remaining {
+                ranges.push(start..=end);
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: Discrete> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.ranges).finish()
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        set.extend(ranges);
+        set
+    }
+}
+
+impl<'a, T: Discrete> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a RangeInclusive<T>;
+    type IntoIter = slice::Iter<'a, RangeInclusive<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn elements(set: &IntervalSet<i32>) -> BTreeSet<i32> {
+        set.iter().flat_map(|r| r.clone()).collect()
+    }
+
+    #[test]
+    fn test_insert_coalesces() {
+        let mut set = set(&[10..=14, 3..=5, 16..=20, 12..=18, 6..=6]);
+        let (start, end) = (30, 29);
+        set.insert(start..=end);
+        assert_eq!(set.ranges(), [3..=6, 10..=20]);
+        assert_eq!(set.len(), 15);
+        assert!(set.contains(6) && set.contains(10) && set.contains(20));
+        assert!(!set.contains(2) && !set.contains(7) && !set.contains(21));
+        assert_eq!(format!("{set:?}"), "{3..=6, 10..=20}");
+    }
+
+    #[test]
+    fn test_extremes() {
+        let set: IntervalSet<u64> = [0..=5, u64::MAX - 1..=u64::MAX, 6..=u64::MAX - 2]
+            .into_iter()
+            .collect();
+        assert_eq!(set.ranges(), [0..=u64::MAX]);
+        assert_eq!(set.len(), 1 << 64);
+        let hole: IntervalSet<u64> = [3..=4, u64::MAX..=u64::MAX].into_iter().collect();
+        assert_eq!(set.difference(&hole).ranges(), [0..=2, 5..=u64::MAX - 1]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..=10, 20..=30]);
+        let b = set(&[5..=25, 28..=40]);
+        assert_eq!(a.union(&b).ranges(), [0..=40]);
+        assert_eq!(a.intersection(&b).ranges(), [5..=10, 20..=25, 28..=30]);
+        assert_eq!(a.difference(&b).ranges(), [0..=4, 26..=27]);
+        assert_eq!(b.difference(&a).ranges(), [11..=19, 31..=40]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_btree_set(
+            a in vec((-20..20, 0..8).prop_map(|(start, len)| start..=start + len), 0..6),
+            b in vec((-20..20, 0..8).prop_map(|(start, len)| start..=start + len), 0..6),
+        ) {
+            let (a, b) = (set(&a), set(&b));
+            let (x, y) = (elements(&a), elements(&b));
+            prop_assert_eq!(a.len(), x.len() as u128);
+            prop_assert!((-30..30).all(|n| a.contains(n) == x.contains(&n)));
+            prop_assert!(a.ranges().windows(2).all(|w| before(*w[0].end(), *w[1].start())));
+            prop_assert_eq!(elements(&a.union(&b)), &x | &y);
+            prop_assert_eq!(elements(&a.intersection(&b)), &x & &y);
+            prop_assert_eq!(elements(&a.difference(&b)), &x - &y);
+        }
+    }
+}
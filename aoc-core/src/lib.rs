@@ -0,0 +1,19 @@
+//! Helpers shared by every year's solutions.
+
+pub mod answer;
+pub mod combinators;
+pub mod diag;
+#[cfg(feature = "differential")]
+pub mod differential;
+pub mod dir;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod parallel;
+pub mod parse;
+pub mod player;
+pub mod render;
+pub mod rng;
+pub mod snapshot;
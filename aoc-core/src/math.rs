@@ -0,0 +1,366 @@
+//! Number theory and exact linear algebra: gcd and lcm, extended Euclid, modular inverses, the
+//! Chinese remainder theorem, Euclidean wrapping, and solving small linear systems over the
+//! rationals without rounding or silent overflow.
+
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+};
+
+/// The signed integer types the functions here work on.
+pub trait Int:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+        })*
+    };
+}
+
+impl_int!(i32, i64, i128);
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Int>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, which is never negative. It's 0 if either number is.
+pub fn lcm<T: Int>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// `(g, x, y)` such that `a * x + b * y == g`, where `g` is [`gcd(a, b)`](gcd).
+pub fn extended_gcd<T: Int>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse<T: Int>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == T::ONE).then(|| x.rem_euclid(m))
+}
+
+/// Wraps `x` into `min..=max`, counting from `min` the way `rem_euclid` counts from 0, so that
+/// one past `max` is `min` and one before `min` is `max`.
+pub fn wrap<T: Int>(x: T, min: T, max: T) -> T {
+    min + (x - min).rem_euclid(max - min + T::ONE)
+}
+
+/// Solves the congruences `x ≡ r (mod m)` for each `(r, m)`, returning the smallest
+/// non-negative `x` and the modulus it repeats with, the lcm of the `m`s. The moduli needn't be
+/// coprime. Returns `None` if the congruences contradict each other, or the lcm doesn't fit an
+/// `i64`.
+///
+/// # Panics
+///
+/// If any modulus isn't positive.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for &(r, n) in congruences {
+        assert!(n > 0, "modulus {n} isn't positive");
+        let (r, n) = (i128::from(r), i128::from(n));
+
+        // x + m * k ≡ r (mod n) has a solution for k iff gcd(m, n) divides r - x.
+        let (g, m_inverse, _) = extended_gcd(m, n);
+        if (r - x) % g != 0 {
+            return None;
+        }
+        let step = n / g;
+        let k = ((r - x) / g).rem_euclid(step) * m_inverse.rem_euclid(step) % step;
+        let modulus = m * step;
+        if modulus > i128::from(i64::MAX) {
+            return None;
+        }
+        x = (x + m * k).rem_euclid(modulus);
+        m = modulus;
+    }
+    Some((x as i64, m as i64))
+}
+
+/// An exact fraction, always in lowest terms with a positive denominator. Arithmetic is checked:
+/// the `checked_` methods return `None` rather than overflow.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { numer: 0, denom: 1 };
+    pub const ONE: Self = Self { numer: 1, denom: 1 };
+
+    /// `numer / denom` in lowest terms, or `None` if `denom` is 0 or either is `i128::MIN`, which
+    /// has no positive counterpart.
+    pub fn new(numer: i128, denom: i128) -> Option<Self> {
+        if denom == 0 || numer == i128::MIN || denom == i128::MIN {
+            return None;
+        }
+        let g = gcd(numer, denom);
+        let (numer, denom) = (numer / g, denom / g);
+        if denom < 0 {
+            Some(Self {
+                numer: -numer,
+                denom: -denom,
+            })
+        } else {
+            Some(Self { numer, denom })
+        }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(self) -> i128 {
+        self.denom
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denom == 1).then_some(self.numer)
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.numer == 0
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let g = gcd(self.denom, rhs.denom);
+        let numer = self
+            .numer
+            .checked_mul(rhs.denom / g)?
+            .checked_add(rhs.numer.checked_mul(self.denom / g)?)?;
+        Self::new(numer, (self.denom / g).checked_mul(rhs.denom)?)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(Self {
+            numer: rhs.numer.checked_neg()?,
+            denom: rhs.denom,
+        })
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // Cancelling crosswise first keeps the intermediate products small.
+        let g1 = gcd(self.numer, rhs.denom).max(1);
+        let g2 = gcd(rhs.numer, self.denom).max(1);
+        Self::new(
+            (self.numer / g1).checked_mul(rhs.numer / g2)?,
+            (self.denom / g2).checked_mul(rhs.denom / g1)?,
+        )
+    }
+
+    /// `None` on overflow, or division by zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(Self::new(rhs.denom, rhs.numer)?)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self {
+            numer: n.into(),
+            denom: 1,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+impl Debug for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// Why a linear system has no unique solution that [`solve`] could find.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// The matrix is singular: the system has no solution or infinitely many.
+    Singular,
+    /// An intermediate value didn't fit an `i128`.
+    Overflow,
+}
+
+/// Solves `matrix * x == rhs` exactly, by Gaussian elimination over the rationals.
+///
+/// For example, `a * (94, 34) + b * (22, 67) == (8400, 5400)` is
+/// `solve([[94, 22], [34, 67]], [8400, 5400])`, with rows as equations and columns as unknowns.
+pub fn solve<const N: usize>(
+    matrix: [[i64; N]; N],
+    rhs: [i64; N],
+) -> Result<[Rational; N], SolveError> {
+    let mut rows: [[Rational; N]; N] = matrix.map(|row| row.map(Rational::from));
+    let mut rhs = rhs.map(Rational::from);
+
+    for col in 0..N {
+        let pivot = (col..N)
+            .find(|&row| !rows[row][col].is_zero())
+            .ok_or(SolveError::Singular)?;
+        rows.swap(col, pivot);
+        rhs.swap(col, pivot);
+
+        for row in 0..N {
+            if row == col || rows[row][col].is_zero() {
+                continue;
+            }
+            let pivot_row = rows[col];
+            let factor = rows[row][col]
+                .checked_div(pivot_row[col])
+                .ok_or(SolveError::Overflow)?;
+            for (cell, &pivot) in rows[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *cell = factor
+                    .checked_mul(pivot)
+                    .and_then(|scaled| cell.checked_sub(scaled))
+                    .ok_or(SolveError::Overflow)?;
+            }
+            rhs[row] = factor
+                .checked_mul(rhs[col])
+                .and_then(|scaled| rhs[row].checked_sub(scaled))
+                .ok_or(SolveError::Overflow)?;
+        }
+    }
+
+    let mut solution = [Rational::ZERO; N];
+    for (i, value) in solution.iter_mut().enumerate() {
+        *value = rhs[i].checked_div(rows[i][i]).ok_or(SolveError::Overflow)?;
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4i128, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap(-1, 0, 99), 99);
+        assert_eq!(wrap(100, 0, 99), 0);
+        assert_eq!(wrap(-201i64, 0, 99), 99);
+        assert_eq!(wrap(7, 1, 6), 1);
+        assert_eq!(wrap(0, 1, 6), 6);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Non-coprime moduli, consistent and not.
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(0, i64::MAX), (1, 2)]), None);
+    }
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(2, 4).unwrap();
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(Rational::new(1, -3).unwrap().to_string(), "-1/3");
+        assert_eq!(Rational::new(1, 0), None);
+        let third = Rational::new(1, 3).unwrap();
+        assert_eq!(half.checked_add(third).unwrap().to_string(), "5/6");
+        assert_eq!(half.checked_sub(half), Some(Rational::ZERO));
+        assert_eq!(half.checked_div(Rational::ZERO), None);
+        assert_eq!(
+            Rational::from(i64::MAX).checked_mul(Rational::from(i64::MAX)),
+            Some(Rational::new(i128::from(i64::MAX).pow(2), 1).unwrap())
+        );
+        let huge = Rational::new(i128::MAX, 1).unwrap();
+        assert_eq!(huge.checked_add(Rational::ONE), None);
+        assert_eq!(Rational::new(i128::MIN, 1), None);
+    }
+
+    #[test]
+    fn test_solve() {
+        let [a, b] = solve([[94, 22], [34, 67]], [8400, 5400]).unwrap();
+        assert_eq!((a.to_integer(), b.to_integer()), (Some(80), Some(40)));
+
+        let [a, b] = solve([[26, 67], [66, 21]], [12748, 12176]).unwrap();
+        assert_eq!(a.to_integer(), None);
+        assert_eq!(b.to_integer(), None);
+
+        let solution = solve([[0, 1, 1], [1, 0, 1], [1, 1, 0]], [5, 4, 3]).unwrap();
+        assert_eq!(
+            solution.map(Rational::to_integer),
+            [Some(1), Some(2), Some(3)]
+        );
+
+        assert_eq!(solve([[1, 2], [2, 4]], [3, 6]), Err(SolveError::Singular));
+        assert_eq!(
+            solve([[1, i64::MAX], [i64::MAX, 1]], [i64::MAX, 1]).map(|_| ()),
+            Ok(())
+        );
+    }
+}
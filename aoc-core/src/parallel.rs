@@ -0,0 +1,96 @@
+//! Iterators that run on rayon's thread pool when the `parallel` feature is on, and serially
+//! otherwise.
+//!
+//! Solvers only chain adaptors that both kinds of iterator have, such as `map`, `filter`,
+//! `flat_map`, `count` and `sum`, so they give the same answers either way. Build with
+//! `--features parallel` to check that a solver still compiles in parallel.
+
+/// The traits a solver needs in scope: `use aoc_core::parallel::prelude::*;`.
+pub mod prelude {
+    #[cfg(feature = "parallel")]
+    pub use rayon::iter::ParallelIterator;
+
+    pub use super::{MaybeIntoParIter, MaybeParIter};
+}
+
+/// `par_iter` with the `parallel` feature, or `iter` without it.
+pub trait MaybeParIter<'a> {
+    type Iter;
+
+    fn maybe_par_iter(&'a self) -> Self::Iter;
+}
+
+#[cfg(feature = "parallel")]
+impl<'a, T: rayon::iter::IntoParallelRefIterator<'a> + ?Sized> MaybeParIter<'a> for T {
+    type Iter = T::Iter;
+
+    fn maybe_par_iter(&'a self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+impl<'a, T: ?Sized + 'a> MaybeParIter<'a> for T
+where
+    &'a T: IntoIterator,
+{
+    type Iter = <&'a T as IntoIterator>::IntoIter;
+
+    fn maybe_par_iter(&'a self) -> Self::Iter {
+        self.into_iter()
+    }
+}
+
+/// `into_par_iter` with the `parallel` feature, or `into_iter` without it.
+pub trait MaybeIntoParIter {
+    type Iter;
+
+    fn maybe_into_par_iter(self) -> Self::Iter;
+}
+
+#[cfg(feature = "parallel")]
+impl<T: rayon::iter::IntoParallelIterator> MaybeIntoParIter for T {
+    type Iter = T::Iter;
+
+    fn maybe_into_par_iter(self) -> Self::Iter {
+        self.into_par_iter()
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+impl<T: IntoIterator> MaybeIntoParIter for T {
+    type Iter = T::IntoIter;
+
+    fn maybe_into_par_iter(self) -> Self::Iter {
+        self.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::prelude::*;
+
+    #[test]
+    fn test_same_answers() {
+        let numbers: Vec<u64> = (0..1000).collect();
+        assert_eq!(
+            numbers
+                .maybe_par_iter()
+                .filter(|&&n| n % 3 == 0)
+                .sum::<u64>(),
+            166_833
+        );
+        assert_eq!(
+            (0..10u64).maybe_into_par_iter().flat_map(|n| 0..n).count(),
+            45
+        );
+
+        let map: HashMap<u8, u8> = (0..10).map(|n| (n, n * 2)).collect();
+        assert_eq!(
+            map.maybe_par_iter().map(|(_, &v)| v as u32).sum::<u32>(),
+            90
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use std::{error::Error, fmt};
+
+use nom::{
+    IResult, Parser,
+    character::complete::line_ending,
+    error::{Error as NomError, ErrorKind},
+};
+
+use crate::input::trim;
+
+/// Matches a line ending, or succeeds without consuming anything at the end of the input.
+pub fn end_of_line(input: &str) -> IResult<&str, &str> {
+    if input.is_empty() {
+        Ok((input, input))
+    } else {
+        line_ending(input)
+    }
+}
+
+/// A puzzle input that could not be parsed, pointing at where parsing stopped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PuzzleParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl PuzzleParseError {
+    /// Builds an error for the byte `offset` into `input`.
+    pub fn at(day: u8, input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            day,
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Builds an error at the start of `span`, which must be a slice of `input`.
+    pub fn at_span(day: u8, input: &str, span: &str, expected: impl Into<String>) -> Self {
+        let offset = (span.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+        Self::at(day, input, offset, expected)
+    }
+
+    /// Builds an error for the first line of a grid that is not as wide as the first line.
+    pub fn ragged(day: u8, input: &str) -> Self {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+
+        let mut offset = 0;
+        for line in input.split_inclusive('\n') {
+            let cells = line.trim_end_matches(['\r', '\n']);
+            let len = cells.chars().count();
+            if len != width {
+                let column = cells
+                    .char_indices()
+                    .nth(width.min(len))
+                    .map_or(cells.len(), |(i, _)| i);
+                return Self::at(day, input, offset + column, format!("a row {width} wide"));
+            }
+            offset += line.len();
+        }
+
+        Self::at(day, input, 0, format!("a row {width} wide"))
+    }
+
+    fn from_nom(day: u8, input: &str, error: NomError<&str>) -> Self {
+        Self::at_span(day, input, error.input, describe(error.code))
+    }
+
+    fn unconsumed(day: u8, input: &str, rest: &str) -> Self {
+        match end_of_line(rest) {
+            Ok((next_line, ending)) if !ending.is_empty() => {
+                Self::at_span(day, input, next_line, "a line matching the puzzle format")
+            }
+            _ => Self::at_span(day, input, rest, "end of input"),
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Tag => "literal text".to_string(),
+        ErrorKind::Char => "a specific character".to_string(),
+        ErrorKind::Digit => "a number".to_string(),
+        ErrorKind::Alpha => "a letter".to_string(),
+        ErrorKind::AlphaNumeric => "a letter or digit".to_string(),
+        ErrorKind::OneOf | ErrorKind::Satisfy => "one of the allowed characters".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".to_string(),
+        ErrorKind::CrLf => "a line ending".to_string(),
+        ErrorKind::Eof => "end of input".to_string(),
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "a valid value".to_string(),
+        kind => format!("input matching {}", kind.description()),
+    }
+}
+
+impl fmt::Display for PuzzleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+        writeln!(
+            f,
+            "day {}: parse error at line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{:gutter$} | {:>column$}", "", "^", column = self.column)
+    }
+}
+
+// `aoc-runner` reports generator errors with `{:#?}`, so render the caret diagnostic there too.
+impl fmt::Debug for PuzzleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for PuzzleParseError {}
+
+/// Runs `parser` over the whole of `input`, failing if it errors or leaves anything unparsed.
+/// A byte order mark and blank lines at the end are [trimmed](trim) first, so `parser` only needs
+/// to accept both LF and CRLF line endings, as the [combinators](crate::combinators) do.
+pub fn parse_puzzle<'a, P>(
+    day: u8,
+    input: &'a str,
+    mut parser: P,
+) -> Result<P::Output, PuzzleParseError>
+where
+    P: Parser<&'a str, Error = NomError<&'a str>>,
+{
+    let input = trim(input);
+    match parser.parse(input) {
+        Ok(("", output)) => Ok(output),
+        Ok((rest, _)) => Err(PuzzleParseError::unconsumed(day, input, rest)),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            Err(PuzzleParseError::from_nom(day, input, error))
+        }
+        Err(nom::Err::Incomplete(_)) => {
+            Err(PuzzleParseError::at(day, input, input.len(), "more input"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{newline, u32},
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_end_of_line() {
+        assert_eq!(end_of_line("\nabc"), Ok(("abc", "\n")));
+        assert_eq!(end_of_line("\r\nabc"), Ok(("abc", "\r\n")));
+        assert_eq!(end_of_line(""), Ok(("", "")));
+        assert!(end_of_line("abc").is_err());
+    }
+
+    fn pairs(input: &str) -> Result<Vec<(u32, u32)>, PuzzleParseError> {
+        parse_puzzle(
+            1,
+            input,
+            separated_list1(newline, separated_pair(u32, tag(","), u32)),
+        )
+    }
+
+    #[test]
+    fn test_parse_puzzle() {
+        assert_eq!(pairs("1,2\n3,4"), Ok(vec![(1, 2), (3, 4)]));
+    }
+
+    #[test]
+    fn test_parse_puzzle_error() {
+        let error = pairs("x,2\n3,4").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.snippet, "x,2");
+        assert_eq!(error.expected, "a number");
+    }
+
+    #[test]
+    fn test_parse_puzzle_unconsumed() {
+        let error = pairs("1,2\n3,4\n5;6\n7,8").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.snippet, "5;6");
+
+        let error = pairs("1,2 ").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.expected, "end of input");
+    }
+
+    #[test]
+    fn test_ragged() {
+        let error = PuzzleParseError::ragged(4, "abc\nabc\nab\nabc");
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.expected, "a row 3 wide");
+
+        let error = PuzzleParseError::ragged(4, "abc\nabcd");
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+
+    #[test]
+    fn test_display() {
+        let error = PuzzleParseError::at(15, "#..\n#.x\n", 6, "one of \".#\"");
+        assert_eq!(
+            error.to_string(),
+            "day 15: parse error at line 2, column 3: expected one of \".#\"
+  |
+2 | #.x
+  |   ^"
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Plays step-based simulations in the terminal.
+//!
+//! Controls are read a line at a time from stdin, so type a command and press enter:
+//! `p` plays or pauses, an empty line steps once while paused, `g <n>` jumps to frame `n`,
+//! `f <fps>` changes the frame rate and `q` quits.
+
+use std::{
+    fmt::Display,
+    io::{self, BufRead},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use crate::render::Canvas;
+
+/// A simulation that advances one frame at a time and can be drawn as text.
+pub trait Simulation: Display {
+    /// Advances one frame, returning `false` once there is nothing left to do.
+    fn step(&mut self) -> bool;
+
+    /// The current frame as an image, for simulations that can be exported.
+    fn canvas(&self) -> Option<&dyn Canvas> {
+        None
+    }
+}
+
+impl<S: Simulation + ?Sized> Simulation for Box<S> {
+    fn step(&mut self) -> bool {
+        (**self).step()
+    }
+
+    fn canvas(&self) -> Option<&dyn Canvas> {
+        (**self).canvas()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Control {
+    TogglePause,
+    Step,
+    Jump(usize),
+    Fps(f64),
+    Quit,
+}
+
+fn parse_control(line: &str) -> Option<Control> {
+    let mut words = line.split_whitespace();
+    let control = match words.next() {
+        None => Control::Step,
+        Some("p" | "pause" | "play") => Control::TogglePause,
+        Some("s" | "step") => Control::Step,
+        Some("g" | "goto") => Control::Jump(words.next()?.parse().ok()?),
+        Some("f" | "fps") => Control::Fps(words.next()?.parse().ok().filter(|&fps| fps > 0.0)?),
+        Some("q" | "quit") => Control::Quit,
+        Some(_) => return None,
+    };
+    words.next().is_none().then_some(control)
+}
+
+pub struct Player {
+    fps: f64,
+    paused: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            fps: 10.0,
+            paused: false,
+        }
+    }
+}
+
+impl Player {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fps(mut self, fps: f64) -> Self {
+        assert!(fps > 0.0, "fps must be positive");
+        self.fps = fps;
+        self
+    }
+
+    /// Starts paused on the first frame instead of playing straight away.
+    pub fn paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Plays the simulation built by `start` until the user quits. Jumping backwards calls `start`
+    /// again and replays from the first frame.
+    pub fn play<S: Simulation>(&self, start: impl Fn() -> S) -> io::Result<()> {
+        let controls = spawn_control_reader();
+
+        let mut sim = start();
+        let mut frame = 0;
+        let mut finished = false;
+        let mut fps = self.fps;
+        let mut paused = self.paused;
+
+        loop {
+            draw(&sim, frame, fps, paused, finished)?;
+
+            let control = if paused || finished {
+                match controls.recv() {
+                    Ok(control) => Some(control),
+                    Err(_) => return Ok(()),
+                }
+            } else {
+                match controls.recv_timeout(Duration::from_secs_f64(1.0 / fps)) {
+                    Ok(control) => Some(control),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => {
+                        // Without stdin there is no way to pause or quit, so just play it out.
+                        while sim.step() {
+                            frame += 1;
+                            draw(&sim, frame, fps, paused, false)?;
+                            thread::sleep(Duration::from_secs_f64(1.0 / fps));
+                        }
+                        return Ok(());
+                    }
+                }
+            };
+
+            match control {
+                None | Some(Control::Step) if !finished => {
+                    finished = !sim.step();
+                    if !finished {
+                        frame += 1;
+                    }
+                }
+                None | Some(Control::Step) => {}
+                Some(Control::TogglePause) => paused = !paused,
+                Some(Control::Jump(target)) => {
+                    (sim, frame, finished) = seek(&start, sim, frame, target);
+                    paused = true;
+                }
+                Some(Control::Fps(new_fps)) => fps = new_fps,
+                Some(Control::Quit) => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Moves `sim`, currently at `frame`, to `target`, replaying from `start` to go backwards.
+/// Stops early if the simulation finishes first.
+fn seek<S: Simulation>(
+    start: impl Fn() -> S,
+    mut sim: S,
+    mut frame: usize,
+    target: usize,
+) -> (S, usize, bool) {
+    if target < frame {
+        sim = start();
+        frame = 0;
+    }
+    while frame < target {
+        if !sim.step() {
+            return (sim, frame, true);
+        }
+        frame += 1;
+    }
+    (sim, frame, false)
+}
+
+fn spawn_control_reader() -> mpsc::Receiver<Control> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if let Some(control) = parse_control(&line)
+                && tx.send(control).is_err()
+            {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+fn draw(
+    sim: &impl Simulation,
+    frame: usize,
+    fps: f64,
+    paused: bool,
+    finished: bool,
+) -> io::Result<()> {
+    clearscreen::clear().map_err(io::Error::other)?;
+    let state = match (finished, paused) {
+        (true, _) => "finished",
+        (false, true) => "paused",
+        (false, false) => "playing",
+    };
+    println!("{sim}");
+    println!();
+    println!(
+        "frame {frame} | {fps} fps | {state} | p: play/pause, enter: step, g <n>: go to frame, \
+         f <fps>: frame rate, q: quit"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use super::*;
+
+    struct Countdown(u32);
+
+    impl fmt::Display for Countdown {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl Simulation for Countdown {
+        fn step(&mut self) -> bool {
+            if self.0 == 0 {
+                return false;
+            }
+            self.0 -= 1;
+            true
+        }
+    }
+
+    #[test]
+    fn test_parse_control() {
+        assert_eq!(parse_control(""), Some(Control::Step));
+        assert_eq!(parse_control("p"), Some(Control::TogglePause));
+        assert_eq!(parse_control("g 120"), Some(Control::Jump(120)));
+        assert_eq!(parse_control("f 2.5"), Some(Control::Fps(2.5)));
+        assert_eq!(parse_control("q"), Some(Control::Quit));
+        assert_eq!(parse_control("f 0"), None);
+        assert_eq!(parse_control("g"), None);
+        assert_eq!(parse_control("x"), None);
+    }
+
+    #[test]
+    fn test_seek() {
+        let start = || Countdown(5);
+        let (sim, frame, finished) = seek(start, start(), 0, 3);
+        assert_eq!((sim.0, frame, finished), (2, 3, false));
+
+        let (sim, frame, finished) = seek(start, sim, frame, 1);
+        assert_eq!((sim.0, frame, finished), (4, 1, false));
+
+        let (sim, frame, finished) = seek(start, sim, frame, 10);
+        assert_eq!((sim.0, frame, finished), (0, 5, true));
+    }
+}
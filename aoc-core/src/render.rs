@@ -0,0 +1,344 @@
+//! Renders grid-shaped puzzle states to PPM, PNG and SVG images.
+//!
+//! A state implements [`Canvas`] by giving its size and a colour for each cell, or is a
+//! [`Grid`] whose cells implement [`Colour`]. PNGs are written uncompressed, which keeps the
+//! encoder small at the cost of larger files.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use glam::IVec2;
+
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// A tile type with a fixed colour in rendered images.
+pub trait Colour {
+    fn colour(&self) -> Rgb;
+}
+
+impl Colour for bool {
+    fn colour(&self) -> Rgb {
+        if *self { [255, 255, 255] } else { [0, 0, 0] }
+    }
+}
+
+/// Something that can be drawn as a grid of coloured cells.
+pub trait Canvas {
+    fn size(&self) -> IVec2;
+
+    fn colour(&self, pos: IVec2) -> Rgb;
+}
+
+impl<T: Colour> Canvas for Grid<T> {
+    fn size(&self) -> IVec2 {
+        Grid::size(self)
+    }
+
+    fn colour(&self, pos: IVec2) -> Rgb {
+        self[pos].colour()
+    }
+}
+
+/// An RGB image, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Draws each cell of `canvas` as a `scale` by `scale` block of pixels.
+    pub fn render(canvas: &(impl Canvas + ?Sized), scale: usize) -> Self {
+        let size = canvas.size();
+        let (width, height) = (size.x as usize * scale, size.y as usize * scale);
+
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(canvas.colour(IVec2::new((x / scale) as i32, (y / scale) as i32)));
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Writes a binary (`P6`) PPM.
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(self.pixels.as_flattened())
+    }
+
+    /// Writes an 8-bit RGB PNG.
+    pub fn write_png(&self, mut out: impl Write) -> io::Result<()> {
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // Bit depth 8, colour type 2 (RGB), default compression, filtering and no interlacing.
+        header.extend([8, 2, 0, 0, 0]);
+        write_chunk(&mut out, b"IHDR", &header)?;
+
+        // Each scanline starts with its filter type, which is always 0 (none).
+        let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width.max(1)) {
+            scanlines.push(0);
+            scanlines.extend(row.as_flattened());
+        }
+        write_chunk(&mut out, b"IDAT", &zlib_stored(&scanlines))?;
+
+        write_chunk(&mut out, b"IEND", &[])
+    }
+
+    /// Writes the image to `path`, choosing PPM or PNG from its extension.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let format = ImageFormat::from_path(path)
+            .filter(|&format| format != ImageFormat::Svg)
+            .ok_or_else(|| unsupported_extension(path))?;
+        let mut out = BufWriter::new(File::create(path)?);
+        match format {
+            ImageFormat::Ppm => self.write_ppm(&mut out)?,
+            ImageFormat::Png => self.write_png(&mut out)?,
+            ImageFormat::Svg => unreachable!(),
+        }
+        out.flush()
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc32(kind.iter().chain(data)).to_be_bytes())
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Writes `canvas` as an SVG with each cell `cell_size` units square. Runs of same-coloured cells
+/// along a row are merged into a single rectangle to keep the file small.
+pub fn write_svg(
+    canvas: &(impl Canvas + ?Sized),
+    cell_size: usize,
+    mut out: impl Write,
+) -> io::Result<()> {
+    let size = canvas.size();
+    let (width, height) = (size.x as usize * cell_size, size.y as usize * cell_size);
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#
+    )?;
+
+    for y in 0..size.y {
+        let mut x = 0;
+        while x < size.x {
+            let colour = canvas.colour(IVec2::new(x, y));
+            let start = x;
+            while x < size.x && canvas.colour(IVec2::new(x, y)) == colour {
+                x += 1;
+            }
+            let [r, g, b] = colour;
+            writeln!(
+                out,
+                r##"<rect x="{}" y="{}" width="{}" height="{cell_size}" fill="#{r:02x}{g:02x}{b:02x}"/>"##,
+                start as usize * cell_size,
+                y as usize * cell_size,
+                (x - start) as usize * cell_size,
+            )?;
+        }
+    }
+
+    writeln!(out, "</svg>")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "ppm" => Some(Self::Ppm),
+            "png" => Some(Self::Png),
+            "svg" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+
+    fn from_path(path: &Path) -> Option<Self> {
+        Self::from_extension(path.extension()?.to_str()?)
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+fn unsupported_extension(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("can't tell the image format of {}", path.display()),
+    )
+}
+
+/// Writes numbered frames, `frame_00000.png`, `frame_00001.png` and so on, into a directory.
+pub struct FrameWriter {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    next: usize,
+}
+
+impl FrameWriter {
+    /// Creates `dir` if needed. `scale` is the size of each cell in pixels, or in SVG units.
+    pub fn new(dir: impl Into<PathBuf>, format: ImageFormat, scale: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            format,
+            scale,
+            next: 0,
+        })
+    }
+
+    /// Writes the next frame, returning its path.
+    pub fn write(&mut self, canvas: &(impl Canvas + ?Sized)) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!(
+            "frame_{:05}.{}",
+            self.next,
+            self.format.extension()
+        ));
+        self.next += 1;
+
+        let mut out = BufWriter::new(File::create(&path)?);
+        match self.format {
+            ImageFormat::Ppm => Image::render(canvas, self.scale).write_ppm(&mut out)?,
+            ImageFormat::Png => Image::render(canvas, self.scale).write_png(&mut out)?,
+            ImageFormat::Svg => write_svg(canvas, self.scale, &mut out)?,
+        }
+        out.flush()?;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Grid<bool> {
+        Grid::from_rows(vec![vec![true, false], vec![false, true]]).unwrap()
+    }
+
+    #[test]
+    fn test_render_scales_cells() {
+        let image = Image::render(&checkerboard(), 2);
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.pixels[0..4], [[255; 3], [255; 3], [0; 3], [0; 3]]);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut out = Vec::new();
+        Image::render(&checkerboard(), 1)
+            .write_ppm(&mut out)
+            .unwrap();
+        assert_eq!(out[..11], *b"P6\n2 2\n255\n");
+        assert_eq!(out.len(), 11 + 2 * 2 * 3);
+    }
+
+    #[test]
+    fn test_png() {
+        let mut out = Vec::new();
+        Image::render(&checkerboard(), 1)
+            .write_png(&mut out)
+            .unwrap();
+        assert_eq!(out[..8], *b"\x89PNG\r\n\x1a\n");
+        assert_eq!(out[12..16], *b"IHDR");
+        assert_eq!(out[out.len() - 8..out.len() - 4], *b"IEND");
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_svg_merges_runs() {
+        let grid = Grid::from_rows(vec![vec![true, true, false]]).unwrap();
+        let mut out = Vec::new();
+        write_svg(&grid, 10, &mut out).unwrap();
+        let svg = String::from_utf8(out).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(svg.contains(r##"<rect x="0" y="0" width="20" height="10" fill="#ffffff"/>"##));
+    }
+}
@@ -0,0 +1,128 @@
+//! Golden-file tests for renderings of puzzle states.
+//!
+//! [`assert_snapshot!`](crate::assert_snapshot) compares a rendering against
+//! `snapshots/<name>.txt` in the calling crate, and fails with a line diff if they differ. Run the
+//! tests with `BLESS=1` to write new and changed snapshots instead, then review them in the diff
+//! before committing.
+
+use std::{fmt::Write, fs, path::Path};
+
+/// The environment variable that makes snapshot assertions write snapshots instead.
+pub const BLESS_VAR: &str = "BLESS";
+
+/// Asserts that `$value`, formatted with `Display`, matches the snapshot called `$name`.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $value:expr $(,)?) => {
+        $crate::snapshot::assert_snapshot_in(
+            ::std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/snapshots")),
+            $name,
+            &$value.to_string(),
+        )
+    };
+}
+
+/// Asserts that `actual` matches `<dir>/<name>.txt`, or writes it there when blessing.
+pub fn assert_snapshot_in(dir: &Path, name: &str, actual: &str) {
+    assert!(
+        !name.is_empty() && !name.contains(['/', '\\']) && !name.starts_with('.'),
+        "invalid snapshot name {name:?}"
+    );
+
+    let path = dir.join(format!("{name}.txt"));
+    let actual = format!("{}\n", actual.trim_end_matches('\n'));
+    let expected = fs::read_to_string(&path).ok();
+    if expected.as_deref() == Some(actual.as_str()) {
+        return;
+    }
+
+    if blessing() {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    match expected {
+        None => panic!(
+            "snapshot {} doesn't exist, run with {BLESS_VAR}=1 to create it:\n{actual}",
+            path.display()
+        ),
+        Some(expected) => panic!(
+            "snapshot {} changed, run with {BLESS_VAR}=1 to update it:\n{}",
+            path.display(),
+            diff(&expected, &actual)
+        ),
+    }
+}
+
+fn blessing() -> bool {
+    std::env::var(BLESS_VAR).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+/// A line diff of two texts, with removed lines marked `-`, added lines `+` and unchanged ones
+/// indented.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let old: Vec<_> = expected.lines().collect();
+    let new: Vec<_> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            writeln!(out, "  {}", old[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            writeln!(out, "- {}", old[i]).unwrap();
+            i += 1;
+        } else {
+            writeln!(out, "+ {}", new[j]).unwrap();
+            j += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb\nc", "a\nx\nc"), "  a\n- b\n+ x\n  c\n");
+        assert_eq!(diff("", "a"), "+ a\n");
+        assert_eq!(diff("a\nb", "b"), "- a\n  b\n");
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let dir = std::env::temp_dir().join(format!("aoc-snapshot-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("grid.txt"), "#.\n.#\n").unwrap();
+
+        assert_snapshot_in(&dir, "grid", "#.\n.#");
+        let changed = panic::catch_unwind(|| assert_snapshot_in(&dir, "grid", "#.\n##"));
+        let missing = panic::catch_unwind(|| assert_snapshot_in(&dir, "other", "#"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let message = *changed.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("- .#\n+ ##"), "{message}");
+        if !blessing() {
+            assert!(missing.is_err());
+        }
+    }
+}